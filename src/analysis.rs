@@ -0,0 +1,728 @@
+use crate::history::RoundRecord;
+use crate::{
+    Action, BlackjackError, Card, CountingSystem, DealerCards, Deck, Decision, Hand, HandValue, PeekRule, Phase,
+    Round, Rules, SideBet, SideBetKind, Strategy, Variant
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Cards by value: index 0 holds the aces, 1 to 8 the twos to nines and 9 every ten valued card.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Composition {
+    counts: [u16; 10],
+}
+
+impl Composition {
+    /// Counts `cards` by value.
+    pub fn new(cards: &[Card]) -> Self {
+        let mut counts: [u16; 10] = [0; 10];
+        for card in cards {
+            counts[(card.value() - 1) as usize] += 1;
+        }
+        Composition { counts }
+    }
+
+    /// A full shoe of `decks` decks.
+    pub fn full(decks: u8) -> Self {
+        let mut counts: [u16; 10] = [4 * decks as u16; 10];
+        counts[9] = 16 * decks as u16;
+        Composition { counts }
+    }
+
+    /// Cards of a blackjack value, 1 for aces up to 10.
+    pub fn count(&self, value: u8) -> u16 {
+        self.counts[(value - 1) as usize]
+    }
+
+    /// Cards left.
+    pub fn total(&self) -> u16 {
+        self.counts.iter().sum()
+    }
+
+    /// Chance the next card is worth `value`, 0 when no card is left.
+    pub fn probability(&self, value: u8) -> f64 {
+        let total: u16 = self.total();
+        if total == 0 {
+            0.0
+        } else {
+            self.count(value) as f64 / total as f64
+        }
+    }
+
+    /// Takes out a card worth `value`, if there is one.
+    pub fn remove(&mut self, value: u8) {
+        let count: &mut u16 = &mut self.counts[(value - 1) as usize];
+        *count = count.saturating_sub(1);
+    }
+
+    /// The cards with one worth `value` taken out.
+    pub fn without(&self, value: u8) -> Self {
+        let mut composition: Composition = *self;
+        composition.remove(value);
+        composition
+    }
+}
+
+fn best_total(hard: u8, ace: bool) -> u8 {
+    if ace && hard + 10 <= 21 { hard + 10 } else { hard }
+}
+
+/// Chances of every final dealer hand.
+#[derive(Copy, Clone, Default)]
+struct DealerOutcomes {
+    /// Dealer stands on the total, under 17 only when the shoe runs dry.
+    totals: [f64; 22],
+    bust: f64,
+    blackjack: f64,
+}
+
+impl DealerOutcomes {
+    fn add(&mut self, other: &DealerOutcomes, probability: f64) {
+        for (total, chance) in self.totals.iter_mut().zip(other.totals.iter()) {
+            *total += chance * probability;
+        }
+        self.bust += other.bust * probability;
+        self.blackjack += other.blackjack * probability;
+    }
+}
+
+/// Exact expected values for a hand against one dealer up card, given the unseen cards.
+/// Values are in units of the hand's bet.
+pub struct EvCalculator {
+    rules: Rules,
+    up_card: u8,
+    dealer: HashMap<(Composition, u8, bool, u8), DealerOutcomes>,
+    player: HashMap<(Composition, u8, bool), f64>,
+}
+
+impl EvCalculator {
+    /// Calculator for hands played against `up_card`, keeping what it works out for later hands.
+    pub fn new(rules: Rules, up_card: Card) -> Self {
+        EvCalculator {
+            rules,
+            up_card: up_card.value(),
+            dealer: HashMap::new(),
+            player: HashMap::new(),
+        }
+    }
+
+    fn dealer_outcomes(&mut self, composition: Composition) -> DealerOutcomes {
+        self.dealer_from(composition, self.up_card, self.up_card == 1, 1)
+    }
+
+    fn dealer_from(&mut self, composition: Composition, hard: u8, ace: bool, cards: u8) -> DealerOutcomes {
+        let mut outcomes: DealerOutcomes = DealerOutcomes::default();
+        let total: u8 = best_total(hard, ace);
+        if cards >= 2 {
+            let soft: bool = ace && hard + 10 <= 21;
+            if cards == 2 && total == 21 {
+                outcomes.blackjack = 1.0;
+                return outcomes;
+            }
+            if total > 21 {
+                outcomes.bust = 1.0;
+                return outcomes;
+            }
+            if total > 17 || (total == 17 && !(soft && self.rules.dealer_hits_soft_17)) {
+                outcomes.totals[total as usize] = 1.0;
+                return outcomes;
+            }
+        }
+        let key = (composition, hard, ace, cards.min(3));
+        if let Some(outcomes) = self.dealer.get(&key) {
+            return *outcomes;
+        }
+        // Having peeked, the hole card can't make a blackjack.
+        let excluded: Option<u8> = match (cards, self.rules.peek, self.up_card) {
+            (1, PeekRule::HoleCard, 1) => Some(10),
+            (1, PeekRule::HoleCard, 10) => Some(1),
+            _ => None
+        };
+        let drawable: u16 = composition.total() - excluded.map_or(0, |v| composition.count(v));
+        if drawable == 0 {
+            outcomes.totals[total.min(21) as usize] = 1.0;
+            return outcomes;
+        }
+        for value in 1..=10 {
+            let count: u16 = composition.count(value);
+            if count == 0 || excluded == Some(value) {
+                continue;
+            }
+            let next: DealerOutcomes = self.dealer_from(
+                composition.without(value),
+                hard + value,
+                ace || value == 1,
+                cards + 1
+            );
+            outcomes.add(&next, count as f64 / drawable as f64);
+        }
+        self.dealer.insert(key, outcomes);
+        outcomes
+    }
+
+    /// Standing on `total`.
+    pub fn stand(&mut self, total: u8, composition: Composition) -> f64 {
+        if total > 21 {
+            return -1.0;
+        }
+        let dealer: DealerOutcomes = self.dealer_outcomes(composition);
+        let mut ev: f64 = dealer.bust - dealer.blackjack;
+        for (dealer_total, chance) in dealer.totals.iter().enumerate() {
+            ev += chance * match total.cmp(&(dealer_total as u8)) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.0,
+                std::cmp::Ordering::Less => -1.0,
+            };
+        }
+        ev
+    }
+
+    /// Best of standing and hitting on a hand.
+    fn hit_or_stand(&mut self, hard: u8, ace: bool, composition: Composition) -> f64 {
+        let total: u8 = best_total(hard, ace);
+        if total >= 21 {
+            return self.stand(total, composition);
+        }
+        let key = (composition, hard, ace);
+        if let Some(ev) = self.player.get(&key) {
+            return *ev;
+        }
+        let ev: f64 = self.stand(total, composition).max(self.hit(hard, ace, composition));
+        self.player.insert(key, ev);
+        ev
+    }
+
+    /// Taking one card and playing on as well as possible.
+    pub fn hit(&mut self, hard: u8, ace: bool, composition: Composition) -> f64 {
+        let mut ev: f64 = 0.0;
+        for value in 1..=10 {
+            let chance: f64 = composition.probability(value);
+            if chance == 0.0 {
+                continue;
+            }
+            let (hard, ace) = (hard + value, ace || value == 1);
+            let outcome: f64 = if best_total(hard, ace) > 21 {
+                -1.0
+            } else {
+                self.hit_or_stand(hard, ace, composition.without(value))
+            };
+            ev += chance * outcome;
+        }
+        ev
+    }
+
+    /// Doubling the bet for exactly one more card.
+    pub fn double(&mut self, hard: u8, ace: bool, composition: Composition) -> f64 {
+        let mut ev: f64 = 0.0;
+        let mut refund: f64 = 0.0;
+        for value in 1..=10 {
+            let chance: f64 = composition.probability(value);
+            if chance > 0.0 {
+                let total: u8 = best_total(hard + value, ace || value == 1);
+                ev += chance * self.stand(total, composition.without(value));
+                if total <= 21 {
+                    refund += chance * self.extra_bet_refund(composition.without(value));
+                }
+            }
+        }
+        2.0 * ev + refund
+    }
+
+    /// Chance the extra bet comes back because a dealer blackjack only takes the original bet.
+    fn extra_bet_refund(&mut self, composition: Composition) -> f64 {
+        if self.rules.original_bets_only() {
+            self.dealer_outcomes(composition).blackjack
+        } else {
+            0.0
+        }
+    }
+
+    /// Chance a hand played like `hit_or_stand` ends without busting.
+    fn live_chance(&mut self, hard: u8, ace: bool, composition: Composition) -> f64 {
+        let total: u8 = best_total(hard, ace);
+        if total > 21 {
+            return 0.0;
+        }
+        if total == 21 || self.stand(total, composition) >= self.hit(hard, ace, composition) {
+            return 1.0;
+        }
+        let mut live: f64 = 0.0;
+        for value in 1..=10 {
+            let chance: f64 = composition.probability(value);
+            if chance > 0.0 {
+                live += chance * self.live_chance(hard + value, ace || value == 1, composition.without(value));
+            }
+        }
+        live
+    }
+
+    /// Splitting a pair of `value`. Assumes no resplits and ignores the cards the other
+    /// hand takes, which makes it a close estimate rather than an exact figure.
+    pub fn split(&mut self, value: u8, composition: Composition) -> f64 {
+        let mut ev: f64 = 0.0;
+        for drawn in 1..=10 {
+            let chance: f64 = composition.probability(drawn);
+            if chance == 0.0 {
+                continue;
+            }
+            let rest: Composition = composition.without(drawn);
+            let (hard, ace) = (value + drawn, value == 1 || drawn == 1);
+            let (outcome, live): (f64, f64) = if value == 1 {
+                (self.stand(best_total(hard, ace), rest), 1.0)
+            } else {
+                let played: f64 = self.hit_or_stand(hard, ace, rest);
+                let doubled: f64 = self.double(hard, ace, rest);
+                if doubled > played {
+                    let live: f64 = (1..=10)
+                        .filter(|v| best_total(hard + v, ace || *v == 1) <= 21)
+                        .map(|v| rest.probability(v))
+                        .sum();
+                    (doubled, live)
+                } else {
+                    (played, self.live_chance(hard, ace, rest))
+                }
+            };
+            // The split hand is returned when it's still standing against a dealer blackjack.
+            ev += chance * (outcome + self.extra_bet_refund(rest) * live / 2.0);
+        }
+        2.0 * ev
+    }
+
+    /// Expected value of a decision on `cards`, `None` when it doesn't apply.
+    pub fn evaluate(&mut self, cards: &[Card], composition: Composition, decision: Decision) -> Option<f64> {
+        let hard: u8 = cards.iter().map(|c| c.value()).sum();
+        let ace: bool = cards.iter().any(|c| c.rank() == 'A');
+        match decision {
+            Decision::Stand => Some(self.stand(best_total(hard, ace), composition)),
+            Decision::Hit => Some(self.hit(hard, ace, composition)),
+            Decision::Double => Some(self.double(hard, ace, composition)),
+            Decision::Split => match cards {
+                [first, second] if first.rank() == second.rank() => {
+                    Some(self.split(first.value(), composition))
+                },
+                _ => None
+            },
+            Decision::Surrender => Some(-0.5),
+            Decision::None => None,
+        }
+    }
+}
+
+/// A recorded round played two ways from one of its decisions.
+pub struct WhatIf {
+    /// Decision the round is forked at, counting from 0.
+    pub decision_index: usize,
+    /// Decision taken in the round.
+    pub original: Decision,
+    /// Decision played instead.
+    pub alternative: Decision,
+    /// Expected value of every legal decision at that point, in units of the hand's bet.
+    /// The split figure is an estimate, see `EvCalculator::split`, the others are exact.
+    /// Empty for the variants the calculator doesn't know.
+    pub evs: Vec<(Decision, f64)>,
+    /// Bet on the hand at the fork.
+    pub bet: u32,
+    /// The round as it was played.
+    pub actual: RoundRecord,
+    /// The round with the alternative, later decisions following the strategy.
+    pub forked: RoundRecord,
+}
+
+impl WhatIf {
+    fn ev(&self, decision: Decision) -> Option<f64> {
+        self.evs.iter().find(|(d, _)| *d == decision).map(|(_, ev)| *ev)
+    }
+
+    /// Expected gain of the alternative over the original decision, in units of the bet.
+    pub fn ev_difference(&self) -> f64 {
+        self.ev(self.alternative).unwrap_or_default() - self.ev(self.original).unwrap_or_default()
+    }
+}
+
+/// Plays a round to the end, taking `decisions` in order and `strategy` afterwards.
+fn play_out(
+    round: &mut Round,
+    mut decisions: impl Iterator<Item = Decision>,
+    strategy: &dyn Strategy
+) -> Result<(), BlackjackError> {
+    while round.phase() != Phase::Cleanup {
+        let action: Action = match round.phase() {
+            Phase::PlayerTurns => {
+                let decision: Decision = match decisions.next() {
+                    Some(decision) => decision,
+                    None => strategy_decision(round, strategy),
+                };
+                Action::Decide(decision)
+            },
+            Phase::DealerTurn => Action::DealerPlay,
+            Phase::Settlement => Action::Settle,
+            _ => break
+        };
+        round.act(action)?;
+    }
+    Ok(())
+}
+
+fn legal_decisions(round: &Round) -> Vec<Decision> {
+    round.legal_actions()
+        .into_iter()
+        .filter_map(|a| match a {
+            Action::Decide(decision) => Some(decision),
+            _ => None
+        })
+        .collect()
+}
+
+fn strategy_decision(round: &Round, strategy: &dyn Strategy) -> Decision {
+    let legal: Vec<Decision> = legal_decisions(round);
+    match round.active_hand() {
+        Some(hand) => strategy.decide(hand, round.dealer_hand().visible_cards(), &legal),
+        None => Decision::Stand
+    }
+}
+
+/// Forks `record` at its `index`th decision, counting from 0, and plays `alternative` instead.
+/// Both rounds play the decisions after the fork with `strategy`.
+pub fn what_if(
+    record: &RoundRecord,
+    index: usize,
+    alternative: Decision,
+    strategy: &dyn Strategy
+) -> Result<WhatIf, BlackjackError> {
+    let missing = || BlackjackError::InvalidConfig(format!("round {} has no decision {}", record.round, index + 1));
+    let original: Decision = record.decisions.get(index).ok_or_else(missing)?.decision;
+    let mut round: Round = Round::from_record(record)?;
+    for action in record.opening_actions() {
+        round.act(action)?;
+    }
+    let mut taken: usize = 0;
+    while taken < index || round.phase() != Phase::PlayerTurns {
+        let action: Action = match round.phase() {
+            Phase::Switch => Action::Switch(record.switched.unwrap_or(false)),
+            Phase::Insurance => Action::Insurance(record.insurance.unwrap_or(false)),
+            Phase::PlayerTurns => {
+                taken += 1;
+                Action::Decide(record.decisions[taken - 1].decision)
+            },
+            _ => return Err(missing())
+        };
+        round.act(action)?;
+    }
+    let hand: &Hand = round.active_hand().ok_or_else(missing)?;
+    let evs: Vec<(Decision, f64)> = match round.dealer_hand().up_card() {
+        Some(up_card) if round.rules().variant == Variant::Classic => {
+            let composition: Composition = Composition::new(&round.unseen_cards());
+            let mut calculator: EvCalculator = EvCalculator::new(*round.rules(), up_card);
+            legal_decisions(&round)
+                .into_iter()
+                .filter_map(|d| calculator.evaluate(hand.cards(), composition, d).map(|ev| (d, ev)))
+                .collect()
+        },
+        _ => Vec::new()
+    };
+    let bet: u32 = hand.bet();
+    let mut actual: Round = round.clone();
+    play_out(&mut actual, record.decisions[index..].iter().map(|d| d.decision), strategy)?;
+    let mut forked: Round = round;
+    forked.act(Action::Decide(alternative))?;
+    play_out(&mut forked, std::iter::empty(), strategy)?;
+    let mut actual: RoundRecord = actual.record().clone();
+    let mut forked: RoundRecord = forked.record().clone();
+    actual.round = record.round;
+    forked.round = record.round;
+    Ok(WhatIf { decision_index: index, original, alternative, evs, bet, actual, forked })
+}
+
+/// Exact odds of a side bet on a shoe.
+#[derive(Copy, Clone, Debug)]
+pub struct SideBetOdds {
+    pub kind: SideBetKind,
+    /// Expected loss per unit bet, negative when the bet favours the player.
+    pub house_edge: f64,
+    /// Chance the bet pays anything.
+    pub hit_frequency: f64,
+}
+
+/// Card of a blackjack value standing in for every card of that value.
+fn card_of_value(value: u8) -> Card {
+    let rank: char = match value {
+        1 => 'A',
+        10 => 'T',
+        _ => (b'0' + value) as char,
+    };
+    Card::of(rank, '\u{2660}')
+}
+
+/// Every hand the dealer can finish with from `cards`, drawing from `composition`,
+/// with its chance scaled by `probability`.
+fn dealer_hands(
+    rules: &Rules,
+    composition: Composition,
+    cards: &mut Vec<Card>,
+    probability: f64,
+    visit: &mut dyn FnMut(&[Card], f64)
+) {
+    let value: HandValue = HandValue::new(cards, false);
+    let hits: bool = value.total < 17 || (value.total == 17 && value.soft && rules.dealer_hits_soft_17);
+    let trick: bool = rules.five_card_trick() && cards.len() >= 5;
+    let total: u16 = composition.total();
+    if (cards.len() >= 2 && (!hits || trick)) || total == 0 {
+        visit(cards, probability);
+        return;
+    }
+    for value in 1..=10 {
+        let count: u16 = composition.count(value);
+        if count == 0 {
+            continue;
+        }
+        cards.push(card_of_value(value));
+        dealer_hands(rules, composition.without(value), cards, probability * count as f64 / total as f64, visit);
+        cards.pop();
+    }
+}
+
+/// Exact house edge and hit frequency of the `kind` side bet dealt from `cards`,
+/// the whole shoe or what is left of it. The dealer is taken to draw right after the deal,
+/// the player's own draws being as likely to be any unseen card.
+pub fn side_bet_odds(kind: SideBetKind, rules: &Rules, cards: &[Card]) -> SideBetOdds {
+    let bet: &dyn SideBet = rules.side_bets.get(kind);
+    let mut hit_frequency: f64 = 0.0;
+    let mut ev: f64 = 0.0;
+    let mut tally = |odds: Option<u32>, probability: f64| match odds {
+        Some(odds) => {
+            hit_frequency += probability;
+            ev += probability * odds as f64;
+        },
+        None => ev -= probability,
+    };
+    let composition: Composition = Composition::new(cards);
+    if bet.dealer_cards() == DealerCards::FinishedHand {
+        // Only values count, so the player's cards are dealt by value as well, in either order.
+        let total: f64 = composition.total() as f64;
+        for first in 1..=10 {
+            let after_first: Composition = composition.without(first);
+            for second in first..=10 {
+                let orders: f64 = if first == second { 1.0 } else { 2.0 };
+                let p_second: f64 = after_first.count(second) as f64 / (total - 1.0);
+                let p_player: f64 = orders * composition.probability(first) * p_second;
+                if p_player == 0.0 {
+                    continue;
+                }
+                let player: [Card; 2] = [card_of_value(first), card_of_value(second)];
+                dealer_hands(rules, after_first.without(second), &mut Vec::new(), p_player, &mut |dealer, p| {
+                    tally(bet.odds(&player, dealer), p)
+                });
+            }
+        }
+    } else {
+        let mut counts: BTreeMap<Card, u32> = BTreeMap::new();
+        for card in cards {
+            *counts.entry(*card).or_default() += 1;
+        }
+        let mut counts: Vec<(Card, u32)> = counts.into_iter().collect();
+        let total: f64 = cards.len() as f64;
+        for i in 0..counts.len() {
+            let (first, count) = counts[i];
+            let p_first: f64 = count as f64 / total;
+            counts[i].1 -= 1;
+            for j in 0..counts.len() {
+                let (second, count) = counts[j];
+                if count == 0 {
+                    continue;
+                }
+                let p_player: f64 = p_first * count as f64 / (total - 1.0);
+                let player: [Card; 2] = [first, second];
+                counts[j].1 -= 1;
+                match bet.dealer_cards() {
+                    DealerCards::NoCards => tally(bet.odds(&player, &[]), p_player),
+                    DealerCards::UpCard => {
+                        for (up_card, count) in counts.iter() {
+                            if *count > 0 {
+                                tally(bet.odds(&player, &[*up_card]), p_player * *count as f64 / (total - 2.0));
+                            }
+                        }
+                    },
+                    _ => {
+                        let rest: Composition = composition.without(first.value).without(second.value);
+                        for up_card in 1..=10 {
+                            let p_up: f64 = rest.probability(up_card);
+                            for hole_card in 1..=10 {
+                                let p: f64 = p_player * p_up * rest.without(up_card).probability(hole_card);
+                                if p > 0.0 {
+                                    tally(bet.odds(&player, &[card_of_value(up_card), card_of_value(hole_card)]), p);
+                                }
+                            }
+                        }
+                    },
+                }
+                counts[j].1 += 1;
+            }
+            counts[i].1 += 1;
+        }
+    }
+    SideBetOdds { kind, house_edge: -ev, hit_frequency }
+}
+
+/// The shoe left at the cut card with a running count of `system` giving about `true_count`.
+/// Every rank leaves in proportion, then cards the system counts high and low are swapped
+/// between the dealt and the unseen cards until the count is reached.
+pub fn counted_shoe(rules: &Rules, system: CountingSystem, true_count: i32) -> Vec<Card> {
+    let mut full: Vec<Card> = Deck::new(rules, 0).fresh_cards();
+    full.sort();
+    let size: usize = full.len();
+    let deck_size: usize = rules.deck_size();
+    let left: usize = ((size as f32 * (1.0 - rules.penetration)).round() as usize).clamp(deck_size / 2, size);
+    let mut ranks: Vec<(char, i32, usize)> = Vec::new();
+    for card in full.iter() {
+        match ranks.last_mut() {
+            Some((rank, _, count)) if *rank == card.rank => *count += 1,
+            _ => ranks.push((card.rank, system.tag(*card), 1)),
+        }
+    }
+    // Unseen cards of each rank, by largest remainder.
+    let mut unseen: Vec<usize> = ranks.iter().map(|(_, _, n)| n * left / size).collect();
+    let mut order: Vec<usize> = (0..ranks.len()).collect();
+    order.sort_by_key(|&r| std::cmp::Reverse(ranks[r].2 * left % size));
+    for &r in order.iter().take(left - unseen.iter().sum::<usize>()) {
+        unseen[r] += 1;
+    }
+    let decks_left: f32 = (left as f32 / deck_size as f32).max(0.5);
+    let target: i32 = (true_count as f32 * decks_left).round() as i32;
+    let count = |unseen: &[usize]| -> i32 {
+        ranks.iter().zip(unseen.iter()).map(|((_, tag, n), u)| tag * (n - u) as i32).sum()
+    };
+    loop {
+        let running: i32 = count(&unseen);
+        let towards: i32 = (target - running).signum();
+        // Deal one more of a rank pushing the count towards the target and put back one that doesn't.
+        let out: Option<usize> = (0..ranks.len())
+            .filter(|&r| unseen[r] > 0 && ranks[r].1 * towards > 0)
+            .max_by_key(|&r| (ranks[r].1 * towards, unseen[r]));
+        let back: Option<usize> = (0..ranks.len())
+            .filter(|&r| unseen[r] < ranks[r].2 && ranks[r].1 * towards <= 0)
+            .max_by_key(|&r| (-ranks[r].1 * towards, ranks[r].2 - unseen[r]));
+        let (Some(out), Some(back)) = (out, back) else {
+            break;
+        };
+        unseen[out] -= 1;
+        unseen[back] += 1;
+        if (count(&unseen) - target).abs() >= (running - target).abs() {
+            unseen[out] += 1;
+            unseen[back] -= 1;
+            break;
+        }
+    }
+    // Suits follow each other so none of them runs out first.
+    let suits: [char; 4] = ['\u{2660}', '\u{2665}', '\u{2663}', '\u{2666}'];
+    let mut cards: Vec<Card> = Vec::new();
+    for (r, (rank, _, _)) in ranks.iter().enumerate() {
+        for i in 0..unseen[r] {
+            cards.push(Card::of(*rank, suits[(i + r) % 4]));
+        }
+    }
+    cards
+}
+
+/// First true count of `system`, searching out from 0 to 10 either way, at which the
+/// `kind` side bet favours the player at the cut card.
+pub fn count_threshold(kind: SideBetKind, rules: &Rules, system: CountingSystem) -> Option<i32> {
+    (0..=10)
+        .flat_map(|n| if n == 0 { vec![0] } else { vec![n, -n] })
+        .find(|&true_count| side_bet_odds(kind, rules, &counted_shoe(rules, system, true_count)).house_edge < 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chart, PeekRule};
+
+    /// Plays `rounds` rounds following `chart`, returning their records.
+    fn play(rules: Rules, chart: &Chart, rounds: u32) -> Vec<RoundRecord> {
+        let mut round: Round = Round::with_seed(rules, 11).unwrap();
+        round.money.deposit(1_000_000);
+        let mut records: Vec<RoundRecord> = Vec::new();
+        while records.len() < rounds as usize {
+            let action: Action = match round.phase() {
+                Phase::Betting => Action::Bet(10),
+                Phase::Dealing => Action::Deal,
+                Phase::Switch => {
+                    let hands: &[Hand] = round.hands();
+                    Action::Switch(chart.switch(&hands[0], &hands[1], round.dealer_hand().visible_cards()))
+                },
+                Phase::Insurance => Action::Insurance(false),
+                Phase::PlayerTurns => Action::Decide(strategy_decision(&round, chart)),
+                Phase::DealerTurn => Action::DealerPlay,
+                Phase::Settlement => Action::Settle,
+                Phase::Cleanup => Action::Cleanup,
+            };
+            round.act(action).unwrap();
+            if action == Action::Settle {
+                records.push(round.record().clone());
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn forked_rounds_play_on_with_the_strategy_of_the_round() {
+        let variants: [Rules; 4] = [
+            Rules { variant: Variant::Spanish21, ..Rules::default() },
+            Rules { variant: Variant::Switch, ..Rules::default() },
+            Rules { variant: Variant::FreeBet, ..Rules::default() },
+            Rules { peek: PeekRule::NoHoleCard, ..Rules::default() },
+        ];
+        for rules in variants {
+            let chart: Chart = Chart::for_rules(&rules);
+            for record in play(rules, &chart, 100).iter().filter(|r| !r.decisions.is_empty()) {
+                // Taking the decision the chart took leaves the round as it was played.
+                let what_if: WhatIf = what_if(record, 0, record.decisions[0].decision, &chart).unwrap();
+                assert_eq!(what_if.forked.decisions, record.decisions, "{:?} round {}", rules.variant, record.round);
+                assert_eq!(what_if.forked.final_wallet, record.final_wallet);
+            }
+        }
+    }
+
+    #[test]
+    fn counted_shoe_counts_spanish_decks_of_48() {
+        let rules: Rules = Rules { variant: Variant::Spanish21, decks: 6, ..Rules::default() };
+        let shoe: Vec<Card> = counted_shoe(&rules, CountingSystem::HiLo, 16);
+        let full: Vec<Card> = Deck::new(&rules, 0).fresh_cards();
+        let tags = |cards: &[Card]| -> i32 { cards.iter().map(|c| CountingSystem::HiLo.tag(*c)).sum() };
+        // 72 cards are a deck and a half of 48.
+        assert_eq!(shoe.len(), 72);
+        assert_eq!(tags(&full) - tags(&shoe), 24);
+    }
+
+    #[test]
+    fn perfect_pairs_odds_on_a_single_deck() {
+        let rules: Rules = Rules { decks: 1, ..Rules::default() };
+        let odds: SideBetOdds = side_bet_odds(SideBetKind::PerfectPairs, &rules, &Deck::new(&rules, 0).fresh_cards());
+        // Two of the 51 cards left make a mixed pair paying 6, one a coloured pair paying 12.
+        assert!((odds.hit_frequency - 3.0 / 51.0).abs() < 1e-12);
+        assert!((odds.house_edge - 24.0 / 51.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn twenty_one_plus_three_odds_match_every_deal() {
+        let rules: Rules = Rules { decks: 1, ..Rules::default() };
+        let cards: Vec<Card> = Deck::new(&rules, 0).fresh_cards();
+        let bet: &dyn SideBet = rules.side_bets.get(SideBetKind::TwentyOnePlusThree);
+        let (mut deals, mut hits, mut net): (f64, f64, f64) = (0.0, 0.0, 0.0);
+        for (i, first) in cards.iter().enumerate() {
+            for (j, second) in cards.iter().enumerate().filter(|(j, _)| *j != i) {
+                for up_card in cards.iter().enumerate().filter(|(k, _)| *k != i && *k != j).map(|(_, c)| c) {
+                    deals += 1.0;
+                    match bet.odds(&[*first, *second], &[*up_card]) {
+                        Some(odds) => {
+                            hits += 1.0;
+                            net += odds as f64;
+                        },
+                        None => net -= 1.0,
+                    }
+                }
+            }
+        }
+        let odds: SideBetOdds = side_bet_odds(SideBetKind::TwentyOnePlusThree, &rules, &cards);
+        assert!((odds.hit_frequency - hits / deals).abs() < 1e-12);
+        assert!((odds.house_edge + net / deals).abs() < 1e-12);
+    }
+}
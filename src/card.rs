@@ -0,0 +1,96 @@
+use crate::error::BlackjackError;
+use serde::{Deserialize, Serialize};
+
+/// A playing card. Tens and faces are worth 10 and aces 1, [`HandValue`](crate::HandValue) counts them as 11 when it helps.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize)]
+pub struct Card {
+    pub(crate) rank: char,
+    pub(crate) suit: char,
+    pub(crate) value: u8
+}
+
+const RANKS: [char; 13] = ['A', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K'];
+const SUITS: [char; 4] = ['\u{2660}', '\u{2665}', '\u{2663}', '\u{2666}'];
+
+impl Card {
+    /// Card of `rank`, A, 2 to 9, T, J, Q or K, and `suit`, one of ♠ ♥ ♣ ♦.
+    pub fn new(rank: char, suit: char) -> Result<Self, BlackjackError> {
+        if RANKS.contains(&rank) && SUITS.contains(&suit) {
+            Ok(Card::of(rank, suit))
+        } else {
+            Err(BlackjackError::InvalidCard { rank, suit })
+        }
+    }
+
+    /// Card the engine knows to be valid.
+    pub(crate) fn of(rank: char, suit: char) -> Self {
+        let value: u8 = match rank {
+            'T' | 'J' | 'Q' | 'K' => 10,
+            'A' => 1,
+            _ => (rank as u8) - b'0'
+        };
+        Card { rank, suit, value }
+    }
+
+    /// A, 2 to 9, T, J, Q or K.
+    pub fn rank(&self) -> char {
+        self.rank
+    }
+
+    /// One of ♠ ♥ ♣ ♦.
+    pub fn suit(&self) -> char {
+        self.suit
+    }
+
+    /// Blackjack value of the card, with aces counted as 1.
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+}
+
+/// Chip denominations on the table, smallest first.
+pub const CHIPS: [u32; 5] = [1, 5, 25, 100, 500];
+
+/// Breaks an amount into the fewest chips, largest first.
+pub fn chips_for(mut amount: u32) -> Vec<u32> {
+    let mut chips: Vec<u32> = Vec::new();
+    for chip in CHIPS.iter().rev() {
+        while amount >= *chip {
+            chips.push(*chip);
+            amount -= chip;
+        }
+    }
+    chips
+}
+
+/// Value of a stack of chips, rejecting anything that isn't a table denomination.
+pub fn chips_value(chips: &[u32]) -> Result<u32, BlackjackError> {
+    let mut total: u32 = 0;
+    for chip in chips {
+        if !CHIPS.contains(chip) {
+            return Err(BlackjackError::InvalidBet(*chip));
+        }
+        total += chip;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cards_outside_the_deck_are_refused() {
+        assert_eq!(Card::new('T', '\u{2665}').unwrap().value(), 10);
+        assert_eq!(Card::new('1', '\u{2665}'), Err(BlackjackError::InvalidCard { rank: '1', suit: '\u{2665}' }));
+        assert_eq!(Card::new('A', 'x'), Err(BlackjackError::InvalidCard { rank: 'A', suit: 'x' }));
+    }
+
+    #[test]
+    fn amounts_break_into_the_fewest_chips() {
+        assert_eq!(chips_for(137), [100, 25, 5, 5, 1, 1]);
+        assert!(chips_for(0).is_empty());
+        assert_eq!(chips_value(&chips_for(137)), Ok(137));
+        assert_eq!(chips_value(&[25, 10]), Err(BlackjackError::InvalidBet(10)));
+    }
+}
@@ -0,0 +1,384 @@
+use blackrust::report::CsvTable;
+use blackrust::{BlackjackError, BlackjackPayout, Config, CountingSystem, Decision, PeekRule, Rules, SideBetKind, Variant};
+use std::path::Path;
+
+pub const USAGE: &str = "\
+Usage: blackrust [COMMAND] [OPTIONS]
+
+Commands:
+  play                 Play at the table (default)
+  train                Play with feedback on every decision
+  simulate             Let a strategy play on its own
+  replay FILE          Replay a hand history and check it against the engine
+  analyze FILE         Show a recorded round or compare one of its decisions
+  chart                Print a strategy chart
+  side-bets [FILE]     Exact house edge of the side bets and the true count
+                       that turns them, on the shoe of a recorded --round if given
+
+Table options:
+  --decks N            Decks in the shoe
+  --penetration F      Fraction of the shoe dealt before shuffling
+  --min-bet N          Table minimum
+  --max-bet N          Table maximum
+  --h17                Dealer hits soft 17
+  --enhc               No hole card, a dealer blackjack takes every bet
+  --obo                No hole card, a dealer blackjack takes the original bets only
+  --surrender          Late surrender
+  --variant GAME       classic, spanish21, switch, double-exposure,
+                       free-bet or pontoon
+  --blackjack-pays P   3:2, 6:5 or 1:1
+
+Options:
+  --config FILE        Settings file, ~/.blackrust/config.toml by default
+  --seed N             Seed for the shoe
+  --rounds N           Rounds to simulate
+  --bankroll N         Starting wallet, added to a player's stored bankroll
+  --bet N              Flat bet for every simulated round
+  --side-bet NAME=N    Side bet placed every round, perfect-pairs, 21+3,
+                       lucky-ladies, buster-blackjack or match-the-dealer,
+                       can be repeated
+  --strategy FILE      Strategy chart to play or grade with
+  --format FORMAT      Output of simulate: text, json (summary and rounds) or csv
+  --table TABLE        Table written as csv: rounds (default), summary or side-bets
+  --count SYSTEM       Show the count before every bet: hi-lo, ko, hi-opt-i or omega-ii,
+                       also the system side-bets counts with
+  --no-color           Don't colour the red suits
+  --history FILE       Append every round to a hand history
+  --ledger FILE        Write the money movements of the session as csv
+                       when play, train or simulate ends
+  --step               Stop after every replayed round
+  --round N            Round to analyze
+  --decision N         Decision of that round to compare
+  --instead DECISION   Alternative to compare it with: hit, stand, double, split or surrender
+  -h, --help           Show this message";
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Play,
+    Train,
+    Simulate,
+    Replay,
+    Analyze,
+    Chart,
+    SideBets,
+    Help,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+/// Everything given on the command line.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub command: Command,
+    pub rules: Rules,
+    /// The rules were set in the config or on the command line, a player's saved rules don't replace them.
+    pub rules_given: bool,
+    pub seed: Option<u64>,
+    pub rounds: u32,
+    pub bankroll: Option<u32>,
+    pub bet: Option<u32>,
+    /// Side bets placed every round, with their amount.
+    pub side_bets: Vec<(SideBetKind, u32)>,
+    pub strategy: Option<String>,
+    pub format: Format,
+    /// Table written by `--format csv`.
+    pub table: Option<CsvTable>,
+    pub history: Option<String>,
+    /// Csv file the session's ledger is exported to.
+    pub ledger: Option<String>,
+    /// History file read by `replay` and `analyze`.
+    pub file: Option<String>,
+    pub step: bool,
+    pub round: Option<u32>,
+    /// Counted from 1, as shown by `analyze`.
+    pub decision: Option<usize>,
+    pub instead: Option<Decision>,
+    /// Counting system to show the count in, if any.
+    pub count: Option<CountingSystem>,
+    pub color: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            command: Command::default(),
+            rules: Rules::default(),
+            rules_given: false,
+            seed: None,
+            rounds: 1000,
+            bankroll: None,
+            bet: None,
+            side_bets: Vec::new(),
+            strategy: None,
+            format: Format::default(),
+            table: None,
+            history: None,
+            ledger: None,
+            file: None,
+            step: false,
+            round: None,
+            decision: None,
+            instead: None,
+            count: None,
+            color: true,
+        }
+    }
+}
+
+/// Flags that change the table rules.
+const RULE_FLAGS: [&str; 10] = [
+    "--h17", "--enhc", "--obo", "--surrender", "--decks", "--penetration", "--min-bet", "--max-bet", "--blackjack-pays", "--variant",
+];
+
+fn invalid(message: String) -> BlackjackError {
+    BlackjackError::InvalidConfig(message)
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, BlackjackError> {
+    value
+        .parse()
+        .map_err(|_| invalid(format!("{} expects a number, got {}", flag, value)))
+}
+
+impl Options {
+    /// Defaults taken from a configuration file.
+    pub fn from_config(config: &Config) -> Self {
+        Options {
+            rules: config.rules(),
+            rules_given: config.table.is_some(),
+            bankroll: config.player.bankroll,
+            bet: config.player.bet,
+            side_bets: config.player.side_bets.iter().map(|(kind, amount)| (*kind, *amount)).collect(),
+            strategy: config.player.strategy.clone(),
+            count: if config.counting.show { Some(config.counting.system) } else { None },
+            color: config.ui.color,
+            ..Options::default()
+        }
+    }
+
+    /// Parses the arguments that follow the program name.
+    /// They override the configuration file given with `--config`, or the default one.
+    pub fn parse(args: &[String]) -> Result<Self, BlackjackError> {
+        let config: Config = match args.iter().position(|a| a == "--config") {
+            Some(i) => {
+                let path: &String = args
+                    .get(i + 1)
+                    .ok_or_else(|| invalid("--config needs a value".to_string()))?;
+                Config::load(Path::new(path))?
+            },
+            None => Config::load_default()?
+        };
+        let mut options: Options = Options::from_config(&config);
+        let mut args = args.iter();
+        let mut first: bool = true;
+        while let Some(arg) = args.next() {
+            let arg: &str = arg.as_str();
+            if first && !arg.starts_with('-') {
+                first = false;
+                options.command = match arg {
+                    "play" => Command::Play,
+                    "train" => Command::Train,
+                    "simulate" => Command::Simulate,
+                    "replay" => Command::Replay,
+                    "analyze" => Command::Analyze,
+                    "chart" => Command::Chart,
+                    "side-bets" => Command::SideBets,
+                    "help" => Command::Help,
+                    _ => return Err(invalid(format!("unknown command {}", arg)))
+                };
+                continue;
+            }
+            first = false;
+            if RULE_FLAGS.contains(&arg) {
+                options.rules_given = true;
+            }
+            match arg {
+                "-h" | "--help" => options.command = Command::Help,
+                "--h17" => options.rules.dealer_hits_soft_17 = true,
+                "--enhc" => options.rules.peek = PeekRule::NoHoleCard,
+                "--obo" => options.rules.peek = PeekRule::OriginalBetsOnly,
+                "--surrender" => options.rules.surrender = true,
+                "--step" => options.step = true,
+                "--no-color" => options.color = false,
+                flag if flag.starts_with("--") => {
+                    let value: &str = args
+                        .next()
+                        .ok_or_else(|| invalid(format!("{} needs a value", flag)))?;
+                    options.set(flag, value)?;
+                },
+                file if options.file.is_none() => options.file = Some(file.to_string()),
+                _ => return Err(invalid(format!("unexpected argument {}", arg)))
+            }
+        }
+        options.validate()?;
+        Ok(options)
+    }
+
+    fn set(&mut self, flag: &str, value: &str) -> Result<(), BlackjackError> {
+        match flag {
+            "--decks" => self.rules.decks = number(flag, value)?,
+            "--penetration" => self.rules.penetration = number(flag, value)?,
+            "--min-bet" => self.rules.min_bet = number(flag, value)?,
+            "--max-bet" => self.rules.max_bet = number(flag, value)?,
+            "--seed" => self.seed = Some(number(flag, value)?),
+            "--rounds" => self.rounds = number(flag, value)?,
+            "--bankroll" => self.bankroll = Some(number(flag, value)?),
+            "--bet" => self.bet = Some(number(flag, value)?),
+            "--config" => {},
+            "--side-bet" => {
+                let (name, amount) = value
+                    .split_once('=')
+                    .ok_or_else(|| invalid(format!("--side-bet expects NAME=AMOUNT, got {}", value)))?;
+                let kind: SideBetKind = SideBetKind::from_id(name).ok_or_else(|| {
+                    let names: Vec<&str> = SideBetKind::ALL.iter().map(|k| k.id()).collect();
+                    invalid(format!("unknown side bet {}, use {}", name, names.join(", ")))
+                })?;
+                let amount: u32 = number(flag, amount)?;
+                self.side_bets.retain(|(k, _)| *k != kind);
+                self.side_bets.push((kind, amount));
+            },
+            "--blackjack-pays" => {
+                self.rules.blackjack_pays = match value {
+                    "3:2" => BlackjackPayout::ThreeToTwo,
+                    "6:5" => BlackjackPayout::SixToFive,
+                    "1:1" => BlackjackPayout::EvenMoney,
+                    _ => return Err(invalid(format!("unknown blackjack payout {}, use 3:2, 6:5 or 1:1", value)))
+                }
+            },
+            "--variant" => {
+                self.rules.variant = match value {
+                    "classic" => Variant::Classic,
+                    "spanish21" => Variant::Spanish21,
+                    "switch" => Variant::Switch,
+                    "double-exposure" => Variant::DoubleExposure,
+                    "free-bet" => Variant::FreeBet,
+                    "pontoon" => Variant::Pontoon,
+                    _ => return Err(invalid(format!("unknown variant {}, use classic, spanish21, switch, double-exposure, free-bet or pontoon", value)))
+                }
+            },
+            "--count" => {
+                self.count = match value {
+                    "hi-lo" => Some(CountingSystem::HiLo),
+                    "ko" => Some(CountingSystem::Ko),
+                    "hi-opt-i" => Some(CountingSystem::HiOptI),
+                    "omega-ii" => Some(CountingSystem::OmegaII),
+                    _ => return Err(invalid(format!("unknown counting system {}, use hi-lo, ko, hi-opt-i or omega-ii", value)))
+                }
+            },
+            "--strategy" => self.strategy = Some(value.to_string()),
+            "--history" => self.history = Some(value.to_string()),
+            "--ledger" => self.ledger = Some(value.to_string()),
+            "--round" => self.round = Some(number(flag, value)?),
+            "--decision" => self.decision = Some(number(flag, value)?),
+            "--format" => {
+                self.format = match value {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => return Err(invalid(format!("unknown format {}, use text, json or csv", value)))
+                }
+            },
+            "--table" => {
+                self.table = match value {
+                    "rounds" => Some(CsvTable::Rounds),
+                    "summary" => Some(CsvTable::Summary),
+                    "side-bets" => Some(CsvTable::SideBets),
+                    _ => return Err(invalid(format!("unknown table {}, use rounds, summary or side-bets", value)))
+                }
+            },
+            "--instead" => {
+                self.instead = match value {
+                    "h" | "hit" => Some(Decision::Hit),
+                    "s" | "stand" => Some(Decision::Stand),
+                    "d" | "double" => Some(Decision::Double),
+                    "p" | "split" => Some(Decision::Split),
+                    "r" | "surrender" => Some(Decision::Surrender),
+                    _ => return Err(invalid(format!("unknown decision {}, use hit, stand, double, split or surrender", value)))
+                }
+            },
+            _ => return Err(invalid(format!("unknown option {}", flag)))
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), BlackjackError> {
+        self.rules.validate()?;
+        if let Some(bet) = self.bet {
+            if bet < self.rules.min_bet || bet > self.rules.max_bet {
+                return Err(invalid(format!(
+                    "--bet {} is outside the table limits {}-{}",
+                    bet, self.rules.min_bet, self.rules.max_bet
+                )));
+            }
+        }
+        for (kind, amount) in self.side_bets.iter() {
+            if *amount == 0 || *amount > self.rules.max_bet {
+                return Err(invalid(format!(
+                    "--side-bet {}={} must be between 1 and the table maximum {}",
+                    kind.id(), amount, self.rules.max_bet
+                )));
+            }
+        }
+        if self.table.is_some() && self.format != Format::Csv {
+            return Err(invalid("--table only applies to --format csv".to_string()));
+        }
+        if self.ledger.is_some() && !matches!(self.command, Command::Play | Command::Train | Command::Simulate) {
+            return Err(invalid("--ledger only applies to play, train and simulate".to_string()));
+        }
+        match self.command {
+            Command::Replay | Command::Analyze if self.file.is_none() => {
+                Err(invalid("a history file is needed".to_string()))
+            },
+            Command::Play | Command::Train | Command::Simulate | Command::Chart | Command::Help if self.file.is_some() => {
+                Err(invalid(format!("unexpected argument {}", self.file.as_deref().unwrap_or_default())))
+            },
+            _ => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Parses `args` against an empty configuration file named after `name`.
+    fn parse(name: &str, args: &str) -> Result<Options, BlackjackError> {
+        let config: PathBuf = std::env::temp_dir().join(format!("blackrust-cli-{}-{}.toml", name, std::process::id()));
+        fs::write(&config, "").unwrap();
+        let mut args: Vec<String> = args.split_whitespace().map(|a| a.to_string()).collect();
+        args.extend(["--config".to_string(), config.to_str().unwrap().to_string()]);
+        let options: Result<Options, BlackjackError> = Options::parse(&args);
+        fs::remove_file(&config).unwrap();
+        options
+    }
+
+    #[test]
+    fn commands_and_flags_set_the_options() {
+        let options: Options = parse("flags", "simulate --decks 2 --h17 --rounds 50 --seed 9 --format json").unwrap();
+        assert_eq!(options.command, Command::Simulate);
+        assert_eq!((options.rules.decks, options.rules.dealer_hits_soft_17), (2, true));
+        assert_eq!((options.rounds, options.seed, options.format), (50, Some(9), Format::Json));
+        assert!(options.rules_given);
+
+        let options: Options = parse("replay", "replay hands.jsonl --step").unwrap();
+        assert_eq!((options.command, options.file.as_deref(), options.step), (Command::Replay, Some("hands.jsonl"), true));
+        assert_eq!(parse("default", "--no-color").unwrap().command, Command::Play);
+        assert!(!parse("rules", "--seed 1").unwrap().rules_given);
+    }
+
+    #[test]
+    fn bad_arguments_are_refused() {
+        for args in ["deal", "--decks", "--decks six", "--variant blackjack", "--frobnicate 1", "replay", "play extra", "chart --ledger a.csv"] {
+            assert!(matches!(parse("bad", args), Err(BlackjackError::InvalidConfig(_))), "{} was accepted", args);
+        }
+    }
+}
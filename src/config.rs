@@ -0,0 +1,204 @@
+use crate::{BlackjackError, CountingSystem, Rules};
+use crate::profile::data_dir;
+use crate::strategy::Chart;
+use crate::side_bet::SideBetKind;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Player defaults, used when the command line doesn't give them.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    /// Strategy chart file, relative to the configuration file.
+    pub strategy: Option<String>,
+    pub bankroll: Option<u32>,
+    pub bet: Option<u32>,
+    /// Amount placed on each side bet every round.
+    pub side_bets: BTreeMap<SideBetKind, u32>,
+}
+
+/// Card counting shown while playing.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CountingConfig {
+    /// System the count is kept in.
+    pub system: CountingSystem,
+    /// Show the count before every bet.
+    pub show: bool,
+}
+
+/// Terminal display.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    /// Red suits in colour.
+    pub color: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        UiConfig { color: true }
+    }
+}
+
+/// Settings read from a TOML file, every section and key being optional:
+///
+/// ```toml
+/// [table]
+/// decks = 6
+/// penetration = 0.8
+/// min_bet = 10
+/// max_bet = 500
+/// dealer_hits_soft_17 = true
+/// peek = "enhc"
+///
+/// [player]
+/// strategy = "charts/h17.txt"
+/// bankroll = 2000
+/// bet = 10
+///
+/// [player.side_bets]
+/// perfect-pairs = 5
+/// "21+3" = 5
+/// buster-blackjack = 5
+///
+/// [table.side_bets.perfect_pairs]
+/// mixed = 5
+/// colored = 10
+/// perfect = 30
+///
+/// [table.side_bets."21+3"]
+/// flush = 4
+/// straight = 10
+///
+/// [table.side_bets.buster_blackjack]
+/// five_cards = 12
+///
+/// [counting]
+/// system = "hi-lo"
+/// show = true
+///
+/// [ui]
+/// color = false
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// `None` when the file has no `[table]` section.
+    pub table: Option<Rules>,
+    pub player: PlayerConfig,
+    pub counting: CountingConfig,
+    pub ui: UiConfig,
+}
+
+impl Config {
+    /// `config.toml` in the data directory, next to profiles and saved games.
+    pub fn default_path() -> PathBuf {
+        data_dir().join("config.toml")
+    }
+
+    /// Reads and validates a configuration file.
+    pub fn load(path: &Path) -> Result<Self, BlackjackError> {
+        let text: String = fs::read_to_string(path)
+            .map_err(|e| BlackjackError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        let mut config: Config = toml::from_str(&text)
+            .map_err(|e| BlackjackError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        if let (Some(strategy), Some(dir)) = (&config.player.strategy, path.parent()) {
+            config.player.strategy = Some(dir.join(strategy).to_string_lossy().to_string());
+        }
+        config.validate().map_err(|e| match e {
+            BlackjackError::InvalidConfig(reason) => {
+                BlackjackError::InvalidConfig(format!("{}: {}", path.display(), reason))
+            },
+            e => e
+        })?;
+        Ok(config)
+    }
+
+    /// The default configuration file if there is one, built-in settings otherwise.
+    pub fn load_default() -> Result<Self, BlackjackError> {
+        let path: PathBuf = Config::default_path();
+        if path.exists() {
+            Config::load(&path)
+        } else {
+            Ok(Config::default())
+        }
+    }
+
+    /// Table rules, the built-in ones when the file doesn't set any.
+    pub fn rules(&self) -> Rules {
+        self.table.unwrap_or_default()
+    }
+
+    /// Checks the rules, the player's bets against the table limits and the strategy chart.
+    pub fn validate(&self) -> Result<(), BlackjackError> {
+        let table: Rules = self.rules();
+        table.validate()?;
+        if let Some(bet) = self.player.bet {
+            if bet < table.min_bet || bet > table.max_bet {
+                return Err(BlackjackError::InvalidConfig(format!(
+                    "player bet {} is outside the table limits {}-{}",
+                    bet, table.min_bet, table.max_bet
+                )));
+            }
+        }
+        for (kind, amount) in self.player.side_bets.iter() {
+            if *amount == 0 || *amount > table.max_bet {
+                return Err(BlackjackError::InvalidConfig(format!(
+                    "player side bet {}={} must be between 1 and the table maximum {}",
+                    kind.id(), amount, table.max_bet
+                )));
+            }
+        }
+        if let Some(strategy) = &self.player.strategy {
+            Chart::load(strategy, &table)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PeekRule;
+
+    #[test]
+    fn sections_and_keys_are_optional() {
+        let config: Config = toml::from_str("[table]\ndecks = 2\npeek = \"obo\"\n\n[player.side_bets]\n\"21+3\" = 5\n").unwrap();
+        let rules: Rules = config.rules();
+        assert_eq!((rules.decks, rules.peek, rules.min_bet), (2, PeekRule::OriginalBetsOnly, Rules::default().min_bet));
+        assert_eq!(config.player.side_bets[&SideBetKind::TwentyOnePlusThree], 5);
+        assert!(config.ui.color);
+
+        let empty: Config = toml::from_str("").unwrap();
+        assert!(empty.table.is_none());
+        assert_eq!(empty.rules().decks, Rules::default().decks);
+    }
+
+    #[test]
+    fn unknown_keys_are_refused() {
+        assert!(toml::from_str::<Config>("[player]\nbankrol = 100\n").is_err());
+        assert!(toml::from_str::<Config>("[colors]\n").is_err());
+    }
+
+    #[test]
+    fn player_bets_must_fit_the_table() {
+        let config: Config = toml::from_str("[table]\nmin_bet = 10\nmax_bet = 100\n\n[player]\nbet = 200\n").unwrap();
+        assert!(matches!(config.validate(), Err(BlackjackError::InvalidConfig(_))));
+        let config: Config = toml::from_str("[player.side_bets]\nperfect-pairs = 0\n").unwrap();
+        assert!(matches!(config.validate(), Err(BlackjackError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn the_strategy_is_found_next_to_the_file() {
+        let dir: PathBuf = std::env::temp_dir().join(format!("blackrust-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("chart.txt"), Chart::basic().to_text()).unwrap();
+        fs::write(dir.join("config.toml"), "[player]\nstrategy = \"chart.txt\"\n").unwrap();
+        let config: Result<Config, BlackjackError> = Config::load(&dir.join("config.toml"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(config.unwrap().player.strategy, Some(dir.join("chart.txt").to_string_lossy().to_string()));
+    }
+}
@@ -0,0 +1,44 @@
+use crate::card::CHIPS;
+use crate::round::{Action, Phase};
+use std::fmt;
+
+/// Everything that can go wrong in the engine, the storage or the configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlackjackError {
+    /// The wallet can't cover a bet, a double, a split, insurance or a side bet.
+    InsufficientFunds { needed: u32, available: u32 },
+    /// The action can't be taken in the phase the round is in, the round is left as it was.
+    IllegalAction(Phase, Action),
+    /// A bet outside the table limits or a chip that isn't one of [`CHIPS`](crate::CHIPS).
+    InvalidBet(u32),
+    /// No card left to deal, even with the discards shuffled back in.
+    EmptyShoe,
+    /// Rules, options, a strategy chart or a hand history that don't make sense.
+    InvalidConfig(String),
+    /// A rank or a suit that isn't in the deck.
+    InvalidCard { rank: char, suit: char },
+    /// Profiles and saved games that can't be read or written.
+    Storage(String),
+}
+
+impl fmt::Display for BlackjackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlackjackError::InsufficientFunds { needed, available } => {
+                write!(f, "Not enough money: {} needed but only {} left", needed, available)
+            },
+            BlackjackError::IllegalAction(phase, action) => {
+                write!(f, "Can't {:?} during {:?}", action, phase)
+            },
+            BlackjackError::InvalidBet(amount) => {
+                write!(f, "{} is not a valid bet, chips are {:?} and bets must be within the table limits", amount, CHIPS)
+            },
+            BlackjackError::EmptyShoe => write!(f, "The shoe is out of cards"),
+            BlackjackError::InvalidConfig(reason) => write!(f, "Invalid configuration: {}", reason),
+            BlackjackError::InvalidCard { rank, suit } => write!(f, "{}{} is not a card", rank, suit),
+            BlackjackError::Storage(reason) => write!(f, "Storage error: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for BlackjackError {}
@@ -0,0 +1,231 @@
+use crate::error::BlackjackError;
+use crate::hand::Hand;
+use crate::money::Money;
+use crate::history::HistoryWriter;
+use crate::profile::SavedGame;
+use crate::report::Simulation;
+use crate::round::{Action, Decision, Phase, Round};
+use crate::rules::Rules;
+use crate::side_bet::SideBetKind;
+use crate::strategy::{Chart, Strategy};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A seat at the table: the round, the chart to play or grade decisions with, the side bets
+/// placed every round and the hand history they are written to.
+#[derive(Default)]
+pub struct Game {
+    round: Round,
+    history: Option<HistoryWriter>,
+    chart: Chart,
+    /// The chart was given with `use_chart` rather than picked for the rules.
+    own_chart: bool,
+    /// Side bets placed every round, with their amount.
+    side_bets: Vec<(SideBetKind, u32)>,
+}
+
+impl Game {
+    /// Seat at a table with `rules`, the shoe shuffled from a random seed.
+    pub fn new(rules: Rules) -> Result<Self, BlackjackError> {
+        Ok(Game { round: Round::new(rules)?, chart: Chart::for_rules(&rules), ..Game::default() })
+    }
+
+    /// Seat whose shoe is shuffled from `seed`, dealing the same cards every time.
+    pub fn with_seed(rules: Rules, seed: u64) -> Result<Self, BlackjackError> {
+        Ok(Game { round: Round::with_seed(rules, seed)?, chart: Chart::for_rules(&rules), ..Game::default() })
+    }
+
+    /// Plays and grades with `chart` instead of the basic strategy of the rules.
+    pub fn use_chart(&mut self, chart: Chart) {
+        self.chart = chart;
+        self.own_chart = true;
+    }
+
+    /// Places `amount` on the `kind` side bet every round.
+    pub fn add_side_bet(&mut self, kind: SideBetKind, amount: u32) {
+        self.side_bets.retain(|(k, _)| *k != kind);
+        self.side_bets.push((kind, amount));
+    }
+
+    /// Puts money in the wallet.
+    pub fn deposit(&mut self, amount: u32) {
+        self.round.money.deposit(amount);
+    }
+
+    /// Appends every settled round to a JSON Lines history file.
+    pub fn record_history(&mut self, path: &str) -> Result<(), BlackjackError> {
+        self.history = Some(HistoryWriter::open(path)?);
+        Ok(())
+    }
+
+    /// Writes the settled round to the history, which is closed after an error.
+    pub fn write_history(&mut self) -> Result<(), BlackjackError> {
+        if let Some(history) = self.history.as_mut() {
+            if let Err(error) = history.write(self.round.record()) {
+                self.history = None;
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// The wallet and its ledger.
+    pub fn money(&self) -> &Money {
+        self.round.money()
+    }
+
+    /// The round being played.
+    pub fn round(&self) -> &Round {
+        &self.round
+    }
+
+    /// Chart the seat plays and grades decisions with.
+    pub fn chart(&self) -> &Chart {
+        &self.chart
+    }
+
+    /// Side bets placed every round, with their amount.
+    pub fn side_bets(&self) -> &[(SideBetKind, u32)] {
+        &self.side_bets
+    }
+
+    /// Moves the round on, see [`Round::act`].
+    pub fn act(&mut self, action: Action) -> Result<(), BlackjackError> {
+        self.round.act(action)
+    }
+
+    /// What the chart plays for the active hand.
+    pub fn chart_decision(&self) -> Decision {
+        let legal: Vec<Decision> = self.round.legal_decisions();
+        match self.round.active_hand() {
+            Some(hand) => self.chart.decide(hand, self.round.dealer_hand().visible_cards(), &legal),
+            None => Decision::Stand
+        }
+    }
+
+    /// Snapshot of the game between two rounds.
+    pub fn save(&self) -> Result<SavedGame, BlackjackError> {
+        self.round.save()
+    }
+
+    /// Picks up a saved game, the money in the wallet joining the saved wallet.
+    pub fn resume(&mut self, saved: SavedGame) -> Result<(), BlackjackError> {
+        let wallet: u32 = self.round.money.wallet;
+        self.round = Round::resume(saved)?;
+        self.round.money.deposit(wallet);
+        // The save brings its own rules back.
+        if !self.own_chart {
+            self.chart = Chart::for_rules(&self.round.rules);
+        }
+        Ok(())
+    }
+
+    /// Moves to a table with other rules, keeping the wallet and the seed of the shoe.
+    pub fn change_rules(&mut self, rules: Rules) -> Result<(), BlackjackError> {
+        let wallet: u32 = self.round.money.wallet;
+        self.round = Round::with_seed(rules, self.round.deck.seed())?;
+        self.round.money.deposit(wallet);
+        if !self.own_chart {
+            self.chart = Chart::for_rules(&rules);
+        }
+        Ok(())
+    }
+
+    /// Lets `strategy` play `rounds` rounds from the current wallet, stopping early when it can't cover a bet.
+    /// Without a fixed `bet` each round bets a random amount up to an eighth of the wallet.
+    pub fn simulate(&mut self, strategy: &dyn Strategy, rounds: u32, bet: Option<u32>) -> Result<Simulation, BlackjackError> {
+        let mut simulation: Simulation = Simulation::new(
+            strategy.name(),
+            self.round.deck.seed(),
+            self.round.rules,
+            self.round.money.wallet,
+        );
+        let mut rng: StdRng = StdRng::seed_from_u64(self.round.deck.seed());
+        let mut games: u32 = 0;
+        while games < rounds {
+            let action: Action = match self.round.phase() {
+                Phase::Betting => {
+                    let rules: &Rules = self.round.rules();
+                    // Every hand of the seat carries the bet.
+                    let wallet: u32 = self.round.money.wallet / rules.base_hands() as u32;
+                    if wallet < rules.min_bet {
+                        break;
+                    }
+                    match bet {
+                        Some(bet) => Action::Bet(bet.min(wallet)),
+                        None => {
+                            let top: u32 = (wallet / 8).clamp(rules.min_bet, rules.max_bet);
+                            Action::Bet(rng.gen_range(rules.min_bet..=top))
+                        }
+                    }
+                },
+                Phase::Switch => {
+                    let hands: &[Hand] = self.round.hands();
+                    Action::Switch(strategy.switch(&hands[0], &hands[1], self.round.dealer_hand().visible_cards()))
+                },
+                Phase::Insurance => Action::Insurance(false),
+                Phase::PlayerTurns => {
+                    let legal: Vec<Decision> = self.round.legal_decisions();
+                    match self.round.active_hand() {
+                        Some(hand) => Action::Decide(strategy.decide(hand, self.round.dealer_hand().visible_cards(), &legal)),
+                        None => break
+                    }
+                },
+                Phase::Dealing => {
+                    let wallet: u32 = self.round.money.wallet;
+                    let placed = |kind: SideBetKind| self.round.side_bets().iter().any(|b| b.kind == kind);
+                    match self.side_bets.iter().find(|(kind, amount)| *amount <= wallet && !placed(*kind)) {
+                        Some((kind, amount)) => Action::SideBet(*kind, *amount),
+                        None => Action::Deal
+                    }
+                },
+                Phase::DealerTurn => Action::DealerPlay,
+                Phase::Settlement => Action::Settle,
+                Phase::Cleanup => Action::Cleanup,
+            };
+            self.round.act(action)?;
+            if action == Action::Settle {
+                self.write_history()?;
+                simulation.add(self.round.record());
+                games += 1;
+            }
+        }
+        simulation.finish(self.round.money.ledger());
+        Ok(simulation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant;
+
+    #[test]
+    fn changing_rules_keeps_the_wallet_and_picks_their_chart() {
+        let mut game: Game = Game::with_seed(Rules::default(), 9).unwrap();
+        game.deposit(300);
+        let spanish: Rules = Rules { variant: Variant::Spanish21, ..Rules::default() };
+        game.change_rules(spanish).unwrap();
+        assert_eq!(game.money().wallet(), 300);
+        assert_eq!(game.round().rules().variant, Variant::Spanish21);
+        assert_eq!(game.round().deck().seed(), 9);
+        assert_eq!(game.chart().name(), Chart::spanish21().name());
+    }
+
+    #[test]
+    fn resuming_adds_the_wallet_to_the_saved_one() {
+        let spanish: Rules = Rules { variant: Variant::Spanish21, ..Rules::default() };
+        let mut saved: Game = Game::with_seed(spanish, 4).unwrap();
+        saved.deposit(200);
+        let mut game: Game = Game::new(Rules::default()).unwrap();
+        game.deposit(50);
+        game.resume(saved.save().unwrap()).unwrap();
+        assert_eq!(game.money().wallet(), 250);
+        assert_eq!(game.chart().name(), Chart::spanish21().name());
+
+        let mut own: Game = Game::new(Rules::default()).unwrap();
+        own.use_chart(Chart::basic());
+        own.resume(saved.save().unwrap()).unwrap();
+        assert_eq!(own.chart().name(), Chart::basic().name());
+    }
+}
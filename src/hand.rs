@@ -0,0 +1,322 @@
+use crate::card::Card;
+use crate::rules::{PeekRule, Rules};
+
+/// Pontoon rank of a five card trick, above any total.
+pub(crate) const FIVE_CARD_TRICK: u8 = 22;
+/// Pontoon rank of a two card 21 on an original hand.
+pub(crate) const PONTOON: u8 = 23;
+
+/// Evaluation of a set of cards, computed from the cards alone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HandValue {
+    /// Best total, counting one ace as 11 when it doesn't bust the hand.
+    pub total: u8,
+    /// An ace is being counted as 11.
+    pub soft: bool,
+    /// Rank of the pair when the hand is exactly two cards of the same rank.
+    pub pair: Option<char>,
+    /// Over 21.
+    pub bust: bool,
+    /// Two card 21 on an original hand, as dealt.
+    pub blackjack: bool,
+    /// Two card 21 on a split or switched hand, which is paid as a regular 21.
+    pub split_21: bool,
+}
+
+impl HandValue {
+    /// `split` marks hands that can't hold a natural, split or Blackjack Switch hands that were switched.
+    pub fn new(cards: &[Card], split: bool) -> Self {
+        let hard: u8 = cards.iter().map(|c| c.value).sum();
+        let has_ace: bool = cards.iter().any(|c| c.rank == 'A');
+        let soft: bool = has_ace && hard + 10 <= 21;
+        let total: u8 = if soft { hard + 10 } else { hard };
+        let pair: Option<char> = match cards {
+            [first, second] if first.rank == second.rank => Some(first.rank),
+            _ => None
+        };
+        let two_card_21: bool = cards.len() == 2 && total == 21;
+        HandValue {
+            total,
+            soft,
+            pair,
+            bust: total > 21,
+            blackjack: two_card_21 && !split,
+            split_21: two_card_21 && split,
+        }
+    }
+
+    /// No ace is counted as 11.
+    pub fn is_hard(&self) -> bool {
+        !self.soft
+    }
+
+    /// Pontoon ranking of a hand of `cards` cards, higher ranks beat lower ones:
+    /// 0 when bust, then the total, a five card trick and a pontoon on top.
+    pub fn pontoon_rank(&self, cards: usize) -> u8 {
+        if self.bust {
+            0
+        } else if self.blackjack {
+            PONTOON
+        } else if cards >= 5 {
+            FIVE_CARD_TRICK
+        } else {
+            self.total
+        }
+    }
+}
+
+/// One of the player's hands with the wager riding on it.
+#[derive(Clone, Default)]
+pub struct Hand {
+    pub(crate) cards: Vec<Card>,
+    pub(crate) split: bool,
+    /// Made by splitting another hand rather than dealt.
+    pub(crate) split_off: bool,
+    pub(crate) bet: u32,
+    /// Stake put up by the house on free doubles and splits.
+    pub(crate) free: u32,
+    pub(crate) doubled: bool,
+    /// Pontoon cards bought so far, each for the opening stake.
+    pub(crate) bought: u8,
+    pub(crate) surrendered: bool,
+    pub(crate) finished: bool,
+    /// Blackjack Switch: the second card came from the other hand, so a two card 21 isn't a natural.
+    pub(crate) switched: bool,
+}
+
+impl Hand {
+    /// A hand holding `cards` with nothing bet on it.
+    pub fn from_cards(cards: Vec<Card>) -> Self {
+        Hand { cards, ..Hand::default() }
+    }
+
+    pub(crate) fn add_card(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    /// Cards in the order they were dealt.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Cards ordered by value, aces first.
+    pub fn sorted_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.cards.clone();
+        cards.sort_by_key(|c| c.value);
+        cards
+    }
+
+    /// The card next to the ace in a two card soft hand.
+    pub fn soft_kicker(&self) -> Option<Card> {
+        match self.cards.as_slice() {
+            [first, second] if first.rank == 'A' => Some(*second),
+            [first, second] if second.rank == 'A' => Some(*first),
+            _ => None
+        }
+    }
+
+    /// The hand comes from splitting a pair.
+    pub fn is_split(&self) -> bool {
+        self.split
+    }
+
+    /// The player's stake on the hand, doubles and Pontoon buys included, free bets left out.
+    pub fn bet(&self) -> u32 {
+        self.bet
+    }
+
+    /// Free bet riding on the hand, a win pays it but it is never returned.
+    pub fn free(&self) -> u32 {
+        self.free
+    }
+
+    /// Everything riding on the hand, the player's bet and the free bet.
+    pub fn action(&self) -> u32 {
+        self.bet + self.free
+    }
+
+    /// The bet was doubled for one more card.
+    pub fn is_doubled(&self) -> bool {
+        self.doubled
+    }
+
+    /// The hand was given up for half its bet.
+    pub fn is_surrendered(&self) -> bool {
+        self.surrendered
+    }
+
+    /// No more decisions can be taken on the hand.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Value of the cards, a two card 21 on a split or switched hand not counting as blackjack.
+    pub fn value(&self) -> HandValue {
+        HandValue::new(&self.cards, self.split || self.switched)
+    }
+
+    /// Two card 21 on a hand as it was dealt.
+    pub fn is_blackjack(&self) -> bool {
+        self.value().blackjack
+    }
+}
+
+/// The dealer's cards, the hole card staying hidden until it is revealed.
+#[derive(Clone, Default)]
+pub struct DealerHand {
+    pub(crate) cards: Vec<Card>,
+    pub(crate) revealed: bool,
+    /// The up card is dealt face down too, as in Pontoon.
+    pub(crate) face_down: bool,
+}
+
+impl DealerHand {
+    pub(crate) fn add_card(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    /// First card dealt to the dealer, face up unless both cards are dealt face down.
+    pub fn up_card(&self) -> Option<Card> {
+        self.cards.first().copied()
+    }
+
+    /// Second card dealt to the dealer, hidden until revealed.
+    pub fn hole_card(&self) -> Option<Card> {
+        if self.revealed {
+            self.cards.get(1).copied()
+        } else {
+            None
+        }
+    }
+
+    /// The hole card has been turned over.
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    pub(crate) fn reveal(&mut self) {
+        self.revealed = true;
+    }
+
+    /// Cards the players can see.
+    pub fn visible_cards(&self) -> &[Card] {
+        if self.revealed {
+            &self.cards
+        } else if self.face_down {
+            &[]
+        } else {
+            &self.cards[..self.cards.len().min(1)]
+        }
+    }
+
+    /// Cards dealt face down and not revealed yet.
+    pub fn hidden_cards(&self) -> usize {
+        self.cards.len() - self.visible_cards().len()
+    }
+
+    /// Value of the whole hand, hole card included.
+    pub fn value(&self) -> HandValue {
+        HandValue::new(&self.cards, false)
+    }
+
+    /// Two card 21, hole card included.
+    pub fn is_blackjack(&self) -> bool {
+        self.value().blackjack
+    }
+
+    /// Checks the hole card for blackjack when the up card is an ace or a ten.
+    /// Without a hole card there is nothing to peek at.
+    pub fn peek(&self, rules: &Rules) -> bool {
+        match (rules.peek, self.up_card()) {
+            (PeekRule::HoleCard, Some(card)) if card.rank == 'A' || card.value == 10 => {
+                self.is_blackjack()
+            },
+            _ => false
+        }
+    }
+
+    /// The dealer draws below 17, and on soft 17 when the rules say so.
+    pub fn should_hit(&self, rules: &Rules) -> bool {
+        let value: HandValue = self.value();
+        value.total < 17 || (value.total == 17 && value.soft && rules.dealer_hits_soft_17)
+    }
+
+    pub(crate) fn clear_hand(&mut self) {
+        self.cards.clear();
+        self.revealed = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Spades of `ranks`, in order.
+    fn cards(ranks: &str) -> Vec<Card> {
+        ranks.split_whitespace().map(|r| Card::of(r.chars().next().unwrap(), '\u{2660}')).collect()
+    }
+
+    #[test]
+    fn aces_count_as_11_until_they_would_bust() {
+        let soft: HandValue = HandValue::new(&cards("A 6"), false);
+        assert_eq!((soft.total, soft.soft), (17, true));
+        let hard: HandValue = HandValue::new(&cards("A 6 T"), false);
+        assert_eq!((hard.total, hard.soft), (17, false));
+        assert!(hard.is_hard());
+        let aces: HandValue = HandValue::new(&cards("A A 9"), false);
+        assert_eq!((aces.total, aces.soft), (21, true));
+        assert!(HandValue::new(&cards("T 6 K"), false).bust);
+    }
+
+    #[test]
+    fn pairs_are_two_cards_of_a_rank() {
+        assert_eq!(HandValue::new(&cards("8 8"), false).pair, Some('8'));
+        assert_eq!(HandValue::new(&cards("K Q"), false).pair, None);
+        assert_eq!(HandValue::new(&cards("8 8 2"), false).pair, None);
+    }
+
+    #[test]
+    fn two_card_21_is_blackjack_only_on_a_dealt_hand() {
+        let dealt: HandValue = HandValue::new(&cards("A K"), false);
+        assert!(dealt.blackjack && !dealt.split_21);
+        let split: HandValue = HandValue::new(&cards("A K"), true);
+        assert!(!split.blackjack && split.split_21);
+        assert!(!HandValue::new(&cards("7 7 7"), false).blackjack);
+    }
+
+    fn dealer(ranks: &str) -> DealerHand {
+        DealerHand { cards: cards(ranks), ..DealerHand::default() }
+    }
+
+    #[test]
+    fn the_hole_card_stays_hidden_until_revealed() {
+        let mut hand: DealerHand = dealer("9 7");
+        assert_eq!(hand.visible_cards(), cards("9").as_slice());
+        assert_eq!(hand.hole_card(), None);
+        assert_eq!(hand.hidden_cards(), 1);
+        hand.reveal();
+        assert_eq!(hand.hole_card(), cards("7").first().copied());
+        assert_eq!(hand.hidden_cards(), 0);
+    }
+
+    #[test]
+    fn the_dealer_peeks_under_aces_and_tens_only() {
+        let rules: Rules = Rules::default();
+        assert!(dealer("A K").peek(&rules));
+        assert!(dealer("Q A").peek(&rules));
+        assert!(!dealer("A 9").peek(&rules));
+        assert!(!dealer("9 A").peek(&rules));
+        let enhc: Rules = Rules { peek: PeekRule::NoHoleCard, ..Rules::default() };
+        assert!(!dealer("A K").peek(&enhc));
+    }
+
+    #[test]
+    fn the_dealer_hits_soft_17_only_when_the_rules_say_so() {
+        let stands: Rules = Rules { dealer_hits_soft_17: false, ..Rules::default() };
+        let hits: Rules = Rules { dealer_hits_soft_17: true, ..Rules::default() };
+        assert!(!dealer("A 6").should_hit(&stands));
+        assert!(dealer("A 6").should_hit(&hits));
+        assert!(!dealer("T 7").should_hit(&hits));
+        assert!(dealer("T 6").should_hit(&stands));
+    }
+}
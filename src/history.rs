@@ -0,0 +1,147 @@
+use crate::{Action, BlackjackError, Card, Decision, HandResult, Rules, SideBetResult};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+/// A decision taken on one of the player's hands.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// Index of the hand among the player's hands.
+    pub hand: usize,
+    /// Cards of the hand when the decision was taken.
+    pub cards: Vec<Card>,
+    /// Dealer's up card, `None` when it is dealt face down.
+    pub up_card: Option<Card>,
+    pub decision: Decision,
+    /// Every decision that was legal at that point.
+    pub legal: Vec<Decision>,
+}
+
+/// Everything that happened in a round, one JSON line in a history file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RoundRecord {
+    /// Number of the round in the session, starting at 1.
+    pub round: u32,
+    /// Seed of the shoe.
+    pub seed: u64,
+    /// Shuffle of the shoe the round was dealt from.
+    pub shuffle: u64,
+    /// Cards dealt from that shuffle before the round started.
+    pub shoe_position: usize,
+    /// Cards on the table when that shoe was reshuffled mid round, left out of it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub held_back: Vec<Card>,
+    pub rules: Rules,
+    /// Wallet before the bet was placed.
+    pub wallet: u32,
+    /// Bet on each base hand.
+    pub bet: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub side_bets: Vec<SideBetResult>,
+    /// Every card taken from the shoe, in order.
+    pub dealt: Vec<Card>,
+    /// Whether the second cards were swapped, Blackjack Switch only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub switched: Option<bool>,
+    /// Whether insurance was taken, `None` when it wasn't offered.
+    pub insurance: Option<bool>,
+    /// What the insurance cost, half of the bets on every hand.
+    #[serde(default)]
+    pub insurance_cost: u32,
+    pub decisions: Vec<DecisionRecord>,
+    /// Cards of every hand at the end of the round.
+    pub player_hands: Vec<Vec<Card>>,
+    pub dealer_hand: Vec<Card>,
+    pub results: Vec<HandResult>,
+    /// Wallet once the round was settled.
+    pub final_wallet: u32,
+}
+
+impl RoundRecord {
+    /// Actions that opened the round: the bet, the side bets and the deal.
+    pub fn opening_actions(&self) -> Vec<Action> {
+        let mut actions: Vec<Action> = vec![Action::Bet(self.bet)];
+        actions.extend(self.side_bets.iter().map(|b| Action::SideBet(b.kind, b.wager)));
+        actions.push(Action::Deal);
+        actions
+    }
+}
+
+/// Appends round records to a JSON Lines file.
+pub struct HistoryWriter {
+    writer: BufWriter<File>,
+}
+
+impl HistoryWriter {
+    /// Opens `path` for appending, creating it if needed.
+    pub fn open(path: &str) -> Result<Self, BlackjackError> {
+        let file: File = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| BlackjackError::Storage(format!("{}: {}", path, e)))?;
+        Ok(HistoryWriter { writer: BufWriter::new(file) })
+    }
+
+    /// Appends `record` as one line and flushes it.
+    pub fn write(&mut self, record: &RoundRecord) -> Result<(), BlackjackError> {
+        let line: String = serde_json::to_string(record)
+            .map_err(|e| BlackjackError::Storage(e.to_string()))?;
+        writeln!(self.writer, "{}", line)
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| BlackjackError::Storage(e.to_string()))
+    }
+}
+
+/// Reads every round of a history file.
+pub fn read_history(path: &str) -> Result<Vec<RoundRecord>, BlackjackError> {
+    let file: File = File::open(path)
+        .map_err(|e| BlackjackError::Storage(format!("{}: {}", path, e)))?;
+    let mut records: Vec<RoundRecord> = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line: String = line.map_err(|e| BlackjackError::Storage(format!("{}: {}", path, e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: RoundRecord = serde_json::from_str(&line)
+            .map_err(|e| BlackjackError::Storage(format!("{} line {}: {}", path, i + 1, e)))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chart, Game};
+    use std::fs;
+
+    fn temp_file(name: &str) -> String {
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!("blackrust-{}-{}.jsonl", name, std::process::id()));
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn recorded_rounds_read_back_in_order() {
+        let path: String = temp_file("history");
+        let mut game: Game = Game::with_seed(Rules::default(), 3).unwrap();
+        game.deposit(1000);
+        game.record_history(&path).unwrap();
+        let wallet: u32 = game.simulate(&Chart::basic(), 20, Some(10)).unwrap().summary.final_wallet;
+        let records: Vec<RoundRecord> = read_history(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(records.len(), 20);
+        assert!(records.iter().enumerate().all(|(i, r)| r.round == i as u32 + 1 && r.seed == 3 && r.bet == 10));
+        assert!(records.windows(2).all(|w| w[0].final_wallet == w[1].wallet));
+        assert_eq!(records[19].final_wallet, wallet);
+    }
+
+    #[test]
+    fn a_broken_line_names_its_number() {
+        let path: String = temp_file("broken-history");
+        fs::write(&path, "\n{}\n").unwrap();
+        let error: BlackjackError = read_history(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(error, BlackjackError::Storage(reason) if reason.contains("line 2")));
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Kind of money movement in the ledger.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Transaction {
+    /// Money put in the wallet.
+    Deposit,
+    /// Main bet of a hand.
+    Bet,
+    /// Extra stake of a double, or of a Pontoon buy.
+    Double,
+    /// Stake of the new hand of a split.
+    Split,
+    /// Insurance taken against a dealer blackjack.
+    Insurance,
+    /// Winnings of a hand, stake included.
+    Payout,
+    /// Insurance paid on a dealer blackjack, stake included.
+    InsurancePayout,
+    /// Stake given back on a tie.
+    PushRefund,
+    /// Half the stake given back on a surrender.
+    SurrenderRefund,
+    /// Doubles and splits returned when a dealer blackjack only takes the original bets.
+    ExtraBetRefund,
+    /// Stake of a side bet.
+    SideBet,
+    /// Winnings of a side bet, stake included.
+    SideBetPayout,
+}
+
+impl Transaction {
+    /// Money leaving the wallet.
+    pub fn is_debit(&self) -> bool {
+        matches!(
+            self,
+            Transaction::Bet | Transaction::Double | Transaction::Split | Transaction::Insurance | Transaction::SideBet
+        )
+    }
+}
+
+/// A single movement of money in or out of the wallet.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    /// Round of the movement, 0 before the first round.
+    pub round: u32,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub transaction: Transaction,
+    /// Signed amount, negative for debits.
+    pub amount: i64,
+    /// Wallet after the transaction.
+    pub balance: u32,
+}
+
+/// Every movement of a wallet, round by round.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    entries: Vec<Entry>,
+    round: u32,
+}
+
+impl Ledger {
+    /// Moves on to the next round, the entries recorded from now on belonging to it.
+    pub fn start_round(&mut self) {
+        self.round += 1;
+    }
+
+    /// Number of the current round, starting at 1.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// Records `amount` signed by its kind, `balance` being the wallet after it.
+    pub fn record(&mut self, transaction: Transaction, amount: u32, balance: u32) {
+        let timestamp: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let amount: i64 = if transaction.is_debit() { -(amount as i64) } else { amount as i64 };
+        self.entries.push(Entry { round: self.round, timestamp, transaction, amount, balance });
+    }
+
+    /// Entries in the order they were recorded.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Net of every transaction of a kind.
+    pub fn total(&self, transaction: Transaction) -> i64 {
+        self.entries
+            .iter()
+            .filter(|e| e.transaction == transaction)
+            .map(|e| e.amount)
+            .sum()
+    }
+
+    /// Won or lost over the session, deposits aside.
+    pub fn session_total(&self) -> i64 {
+        self.entries
+            .iter()
+            .filter(|e| e.transaction != Transaction::Deposit)
+            .map(|e| e.amount)
+            .sum()
+    }
+
+    /// Wallet at the end of every round, money at stake mid round doesn't count.
+    fn settled_balances(&self) -> impl Iterator<Item = u32> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(i, e)| self.entries.get(i + 1).is_none_or(|next| next.round != e.round))
+            .map(|(_, e)| e.balance)
+    }
+
+    /// Highest wallet at the end of a round.
+    pub fn peak_bankroll(&self) -> u32 {
+        self.settled_balances().max().unwrap_or_default()
+    }
+
+    /// Largest drop from a peak to a later low.
+    pub fn max_drawdown(&self) -> u32 {
+        let mut peak: u32 = 0;
+        let mut drawdown: u32 = 0;
+        for balance in self.settled_balances() {
+            peak = peak.max(balance);
+            drawdown = drawdown.max(peak - balance);
+        }
+        drawdown
+    }
+
+    /// The entries as CSV, one row each.
+    pub fn to_csv(&self) -> String {
+        let mut csv: String = String::from("round,timestamp,transaction,amount,balance\n");
+        for entry in self.entries.iter() {
+            csv.push_str(&format!(
+                "{},{},{:?},{},{}\n",
+                entry.round, entry.timestamp, entry.transaction, entry.amount, entry.balance
+            ));
+        }
+        csv
+    }
+
+    /// Writes the CSV to `path`.
+    pub fn export_csv(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rounds of a bet of 10 from a wallet of 100, each paying back `payouts[i]`.
+    fn ledger(payouts: &[u32]) -> Ledger {
+        let mut ledger: Ledger = Ledger::default();
+        let mut balance: u32 = 100;
+        ledger.record(Transaction::Deposit, balance, balance);
+        for payout in payouts {
+            ledger.start_round();
+            balance -= 10;
+            ledger.record(Transaction::Bet, 10, balance);
+            if *payout > 0 {
+                balance += payout;
+                ledger.record(Transaction::Payout, *payout, balance);
+            }
+        }
+        ledger
+    }
+
+    #[test]
+    fn totals_sign_debits_and_leave_deposits_out_of_the_session() {
+        let ledger: Ledger = ledger(&[20, 0, 25]);
+        assert_eq!(ledger.total(Transaction::Deposit), 100);
+        assert_eq!(ledger.total(Transaction::Bet), -30);
+        assert_eq!(ledger.total(Transaction::Payout), 45);
+        assert_eq!(ledger.total(Transaction::Insurance), 0);
+        assert_eq!(ledger.session_total(), 15);
+    }
+
+    #[test]
+    fn peak_and_drawdown_only_count_settled_rounds() {
+        // 100, 110, 100, 90, 105: the bets taken mid round never show.
+        let ledger: Ledger = ledger(&[20, 0, 0, 25]);
+        assert_eq!(ledger.peak_bankroll(), 110);
+        assert_eq!(ledger.max_drawdown(), 20);
+    }
+
+    #[test]
+    fn drawdown_runs_from_the_peak_to_a_later_low() {
+        // 100, 90, 80, 90, 100, 110: the drop counts from the first 100, not from the later peak.
+        let ledger: Ledger = ledger(&[0, 0, 20, 20, 20]);
+        assert_eq!(ledger.peak_bankroll(), 110);
+        assert_eq!(ledger.max_drawdown(), 20);
+
+        let empty: Ledger = Ledger::default();
+        assert_eq!(empty.peak_bankroll(), 0);
+        assert_eq!(empty.max_drawdown(), 0);
+    }
+
+    #[test]
+    fn csv_has_a_row_per_entry() {
+        let csv: String = ledger(&[20]).to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], "round,timestamp,transaction,amount,balance");
+        assert!(rows[2].starts_with("1,") && rows[2].ends_with(",Bet,-10,90"));
+    }
+
+    #[test]
+    fn export_writes_the_csv_to_a_file() {
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!("blackrust-ledger-{}.csv", std::process::id()));
+        let path: &str = path.to_str().unwrap();
+        let ledger: Ledger = ledger(&[20, 0]);
+        ledger.export_csv(path).unwrap();
+        let written: String = fs::read_to_string(path).unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(written, ledger.to_csv());
+    }
+}
@@ -0,0 +1,64 @@
+//! Blackjack engine behind the `blackrust` command line game.
+//!
+//! The pieces build on each other:
+//!
+//! - [`Card`] and the chip helpers, [`Deck`] for a seeded shoe and its counts.
+//! - [`Rules`] for the table, [`Hand`], [`DealerHand`] and [`HandValue`] for hand evaluation.
+//! - [`Round`], the engine: a state machine driven by [`Action`]s through its [`Phase`]s.
+//! - [`Strategy`] and [`Chart`] to take decisions, [`Game`] to seat a player with them and
+//!   [`Game::simulate`] rounds into a [`Simulation`].
+//! - [`history`], [`replay`] and [`analysis`] to record, re-run and evaluate rounds and side bets.
+//!
+//! ```no_run
+//! use blackrust::{Chart, Game, Rules};
+//!
+//! let mut game = Game::with_seed(Rules::default(), 42)?;
+//! game.deposit(1000);
+//! let simulation = game.simulate(&Chart::basic(), 10_000, Some(10))?;
+//! println!("{:+.3}%", simulation.summary.edge * 100.0);
+//! # Ok::<(), blackrust::BlackjackError>(())
+//! ```
+
+/// Expected values of decisions, what-if forks of recorded rounds and side bet odds.
+pub mod analysis;
+mod card;
+/// The `config.toml` file with the table, player, counting and display settings.
+pub mod config;
+mod error;
+mod game;
+mod hand;
+/// Rounds recorded as JSON Lines.
+pub mod history;
+/// Money movements of a wallet.
+pub mod ledger;
+mod money;
+mod poker;
+/// Player profiles and saved games, stored as JSON.
+pub mod profile;
+/// Re-running recorded rounds through the engine.
+pub mod replay;
+/// Simulation summaries and their csv tables.
+pub mod report;
+mod round;
+mod rules;
+mod shoe;
+mod side_bet;
+mod strategy;
+
+pub use card::{chips_for, chips_value, Card, CHIPS};
+pub use config::Config;
+pub use error::BlackjackError;
+pub use game::Game;
+pub use hand::{DealerHand, Hand, HandValue};
+pub use ledger::{Ledger, Transaction};
+pub use money::Money;
+pub use poker::{poker_rank, three_card_hand, PokerHand};
+pub use report::Simulation;
+pub use round::{Action, Decision, GameStatus, HandResult, Phase, Round};
+pub use rules::{BlackjackPayout, PeekRule, Rules, Variant};
+pub use shoe::{CountingSystem, Deck};
+pub use side_bet::{
+    pair_kind, BusterBlackjack, DealerCards, LuckyLadies, MatchTheDealer, PairKind, PerfectPairs, SideBet, SideBetKind, SideBetResult,
+    SideBetTiming, SideBets, TwentyOnePlusThree,
+};
+pub use strategy::{Chart, Strategy};
@@ -1,43 +1,184 @@
-pub mod logic;
-use logic::*; 
+mod cli;
+mod session;
+mod view;
+
+use blackrust::analysis::SideBetOdds;
+use blackrust::replay::Replay;
+use blackrust::{
+    analysis, history, replay, BlackjackError, Card, Chart, CountingSystem, Deck, Game, Round, Rules, SideBetKind,
+    Simulation
+};
+use cli::{Command, Format, Options};
+use session::Session;
 
 fn main() {
-    let mut game: Game = Game::default();
-    let mut input: String = String::new();
-    println!("Player-Machine [1] or Machine-Machine [2]: ");
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("failed to read value");
-    let choice = input.trim().parse::<usize>();
-    if let Ok(i) = choice {
-        if i == 1 {
-            game.play()
-        } else if i == 2 {
-            println!("Put limit: ");
-            let mut value: String = String::new();
-            std::io::stdin()
-                .read_line(&mut value)
-                .expect("failed to read limit value");
-            let limit: u32 = match value.trim().parse() {
-                Ok(i) => i,
-                Err(_) => 0
-            };
-            let stadistics: Vec<GameStatus> = game.basic_strategy_play(limit);
-            let mut wins: u32 = 0;
-            let mut loses: u32 = 0;
-            let mut ties: u32 = 0;
-            if stadistics.is_empty() {
-                println!("Error: list empty")
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result: Result<(), BlackjackError> = Options::parse(&args).and_then(|options| run(&options));
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        eprintln!("Run with --help to see the options.");
+        std::process::exit(1);
+    }
+}
+
+fn run(options: &Options) -> Result<(), BlackjackError> {
+    match options.command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Play | Command::Train => {
+            let mut session: Session = Session::new(new_game(options)?, options.color);
+            if options.rules_given {
+                session.keep_rules();
+            }
+            if let Some(system) = options.count {
+                session.show_count(system);
+            }
+            if options.command == Command::Train {
+                session.train();
             } else {
-                for status in stadistics {
-                    match status {
-                        GameStatus::Win => wins += 1,
-                        GameStatus::Tie => ties += 1,
-                        GameStatus::Lose => loses += 1
-                    }
-                }
-                println!("Wins: {}, Ties: {}, Loses: {}", wins, ties, loses)
+                session.play();
+            }
+            export_ledger(options, session.game())?;
+        },
+        Command::Simulate => simulate(options)?,
+        Command::Replay => replay(options)?,
+        Command::Analyze => analyze(options)?,
+        Command::Chart => print!("{}", chart(options)?.to_text()),
+        Command::SideBets => side_bets(options)?,
+    }
+    Ok(())
+}
+
+fn chart(options: &Options) -> Result<Chart, BlackjackError> {
+    chart_for(options, &options.rules)
+}
+
+fn chart_for(options: &Options, rules: &Rules) -> Result<Chart, BlackjackError> {
+    match &options.strategy {
+        Some(path) => Chart::load(path, rules),
+        None => Ok(Chart::for_rules(rules))
+    }
+}
+
+fn new_game(options: &Options) -> Result<Game, BlackjackError> {
+    let mut game: Game = match options.seed {
+        Some(seed) => Game::with_seed(options.rules, seed)?,
+        None => Game::new(options.rules)?
+    };
+    if options.strategy.is_some() {
+        game.use_chart(chart(options)?);
+    }
+    if let Some(path) = &options.history {
+        game.record_history(path)?;
+    }
+    for (kind, amount) in options.side_bets.iter() {
+        game.add_side_bet(*kind, *amount);
+    }
+    if let Some(bankroll) = options.bankroll {
+        game.deposit(bankroll);
+    }
+    Ok(game)
+}
+
+fn export_ledger(options: &Options, game: &Game) -> Result<(), BlackjackError> {
+    match &options.ledger {
+        Some(path) => game
+            .money()
+            .ledger()
+            .export_csv(path)
+            .map_err(|e| BlackjackError::Storage(format!("{}: {}", path, e))),
+        None => Ok(())
+    }
+}
+
+fn simulate(options: &Options) -> Result<(), BlackjackError> {
+    let strategy: Chart = chart(options)?;
+    let mut game: Game = new_game(options)?;
+    if options.bankroll.is_none() {
+        game.deposit(10000);
+    }
+    let simulation: Simulation = game.simulate(&strategy, options.rounds, options.bet)?;
+    export_ledger(options, &game)?;
+    match options.format {
+        Format::Text => view::simulation(&simulation),
+        Format::Json => println!("{}", simulation.to_json()),
+        Format::Csv => print!("{}", simulation.to_csv(options.table.unwrap_or_default())),
+    }
+    Ok(())
+}
+
+/// Replays a history file, stopping after each round until Enter is pressed with `--step`.
+fn replay(options: &Options) -> Result<(), BlackjackError> {
+    let replays: Vec<Replay> = replay::replay_file(options.file.as_deref().unwrap_or_default())?;
+    let mut failed: usize = 0;
+    for replay in replays.iter() {
+        if options.step {
+            view::round(&replay.record, options.color);
+        }
+        if !replay.is_ok() {
+            failed += 1;
+            println!("Round {} doesn't match:", replay.record.round);
+            for mismatch in replay.mismatches.iter() {
+                println!("  {}", mismatch);
             }
         }
+        if options.step {
+            println!("[Enter] next round, [q] quit ");
+            if session::read_line().is_none_or(|input| input == "q") {
+                break;
+            }
+        }
+    }
+    println!("{} rounds replayed, {} mismatched", replays.len(), failed);
+    Ok(())
+}
+
+fn analyze(options: &Options) -> Result<(), BlackjackError> {
+    let path: &str = options.file.as_deref().unwrap_or_default();
+    let records = history::read_history(path)?;
+    let round: u32 = options
+        .round
+        .ok_or(BlackjackError::InvalidConfig("analyze needs --round".to_string()))?;
+    let record = records
+        .iter()
+        .find(|r| r.round == round)
+        .ok_or(BlackjackError::InvalidConfig(format!("no round {} in {}", round, path)))?;
+    view::round(record, options.color);
+    if let (Some(index), Some(alternative)) = (options.decision, options.instead) {
+        let chart: Chart = chart_for(options, &record.rules)?;
+        view::what_if(&analysis::what_if(record, index.saturating_sub(1), alternative, &chart)?);
+    }
+    Ok(())
+}
+
+fn side_bets(options: &Options) -> Result<(), BlackjackError> {
+    let (rules, cards): (Rules, Vec<Card>) = match options.file.as_deref() {
+        Some(path) => {
+            let records = history::read_history(path)?;
+            let round: u32 = options
+                .round
+                .ok_or(BlackjackError::InvalidConfig("side-bets needs --round with a history".to_string()))?;
+            let record = records
+                .iter()
+                .find(|r| r.round == round)
+                .ok_or(BlackjackError::InvalidConfig(format!("no round {} in {}", round, path)))?;
+            (record.rules, Round::from_record(record)?.unseen_cards())
+        },
+        None => (options.rules, Deck::new(&options.rules, 0).fresh_cards())
+    };
+    let kinds: Vec<SideBetKind> = if options.side_bets.is_empty() {
+        SideBetKind::ALL.to_vec()
+    } else {
+        options.side_bets.iter().map(|(kind, _)| *kind).collect()
+    };
+    let system: CountingSystem = options.count.unwrap_or_default();
+    println!("{} cards in the shoe", cards.len());
+    for kind in kinds {
+        let odds: SideBetOdds = analysis::side_bet_odds(kind, &rules, &cards);
+        view::side_bet_odds(&odds);
+        match analysis::count_threshold(kind, &rules, system) {
+            Some(true_count) => println!("  favours the player at a true count of {:+} at the cut card", true_count),
+            None => println!("  never favours the player between true counts of -10 and +10"),
+        }
     }
+    Ok(())
 }
@@ -0,0 +1,64 @@
+use crate::error::BlackjackError;
+use crate::ledger::{Ledger, Transaction};
+
+/// The player's wallet and the ledger of everything that went through it.
+#[derive(Clone, Default)]
+pub struct Money {
+    pub(crate) wallet: u32,
+    pub(crate) last_bet: u32,
+    pub(crate) ledger: Ledger,
+}
+
+impl Money {
+    /// Money the player holds, bets on the table left out.
+    pub fn wallet(&self) -> u32 {
+        self.wallet
+    }
+
+    /// Main bet of the last round, 0 before the first.
+    pub fn last_bet(&self) -> u32 {
+        self.last_bet
+    }
+
+    /// Every movement of the wallet.
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    pub(crate) fn deposit(&mut self, amount: u32) {
+        self.credit(amount, Transaction::Deposit);
+    }
+
+    pub(crate) fn debit(&mut self, amount: u32, transaction: Transaction) -> Result<(), BlackjackError> {
+        if amount > self.wallet {
+            return Err(BlackjackError::InsufficientFunds { needed: amount, available: self.wallet });
+        }
+        self.wallet -= amount;
+        self.ledger.record(transaction, amount, self.wallet);
+        Ok(())
+    }
+
+    pub(crate) fn credit(&mut self, amount: u32, transaction: Transaction) {
+        if amount > 0 {
+            self.wallet += amount;
+            self.ledger.record(transaction, amount, self.wallet);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debits_beyond_the_wallet_are_refused() {
+        let mut money: Money = Money::default();
+        money.deposit(20);
+        assert_eq!(
+            money.debit(25, Transaction::Bet),
+            Err(BlackjackError::InsufficientFunds { needed: 25, available: 20 })
+        );
+        assert_eq!(money.wallet(), 20);
+        assert_eq!(money.ledger().entries().len(), 1);
+    }
+}
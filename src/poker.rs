@@ -0,0 +1,77 @@
+use crate::card::Card;
+
+/// Three card poker hands that pay, from the weakest to the best.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PokerHand {
+    Flush,
+    Straight,
+    ThreeOfAKind,
+    StraightFlush,
+    /// Three of a kind in the same suit, only possible with several decks.
+    SuitedTrips,
+}
+
+/// Poker rank of a card, 2 to 14 with the ace high.
+pub fn poker_rank(card: Card) -> u8 {
+    match card.rank {
+        'T' => 10,
+        'J' => 11,
+        'Q' => 12,
+        'K' => 13,
+        'A' => 14,
+        rank => rank as u8 - b'0',
+    }
+}
+
+/// Best paying hand the three cards make, `None` for a high card or a pair.
+/// The ace plays high or low in a straight but doesn't wrap around, K-A-2 isn't one.
+pub fn three_card_hand(cards: [Card; 3]) -> Option<PokerHand> {
+    let mut ranks: [u8; 3] = cards.map(poker_rank);
+    ranks.sort();
+    let suited: bool = cards.iter().all(|c| c.suit == cards[0].suit);
+    let trips: bool = ranks[0] == ranks[2];
+    let straight: bool = (ranks[0] + 1 == ranks[1] && ranks[1] + 1 == ranks[2]) || ranks == [2, 3, 14];
+    match (suited, trips, straight) {
+        (true, true, _) => Some(PokerHand::SuitedTrips),
+        (true, _, true) => Some(PokerHand::StraightFlush),
+        (false, true, _) => Some(PokerHand::ThreeOfAKind),
+        (_, _, true) => Some(PokerHand::Straight),
+        (true, _, _) => Some(PokerHand::Flush),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three card hand of `ranks` in `suits`, written `s`, `h`, `c` or `d`.
+    fn hand(ranks: &str, suits: &str) -> Option<PokerHand> {
+        let suit = |s: char| match s {
+            's' => '\u{2660}',
+            'h' => '\u{2665}',
+            'c' => '\u{2663}',
+            _ => '\u{2666}',
+        };
+        let cards: Vec<Card> = ranks.chars().zip(suits.chars()).map(|(rank, s)| Card::of(rank, suit(s))).collect();
+        three_card_hand([cards[0], cards[1], cards[2]])
+    }
+
+    #[test]
+    fn three_card_hands_rank_from_flush_to_suited_trips() {
+        assert_eq!(hand("29K", "sss"), Some(PokerHand::Flush));
+        assert_eq!(hand("9TJ", "shs"), Some(PokerHand::Straight));
+        assert_eq!(hand("777", "shc"), Some(PokerHand::ThreeOfAKind));
+        assert_eq!(hand("QKA", "ddd"), Some(PokerHand::StraightFlush));
+        assert_eq!(hand("777", "hhh"), Some(PokerHand::SuitedTrips));
+        assert_eq!(hand("77K", "shs"), None);
+        assert_eq!(hand("29K", "shs"), None);
+    }
+
+    #[test]
+    fn the_ace_plays_high_or_low_without_wrapping() {
+        assert_eq!(hand("A23", "shs"), Some(PokerHand::Straight));
+        assert_eq!(hand("QKA", "shs"), Some(PokerHand::Straight));
+        assert_eq!(hand("KA2", "shs"), None);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::ledger::Ledger;
+use crate::{BlackjackError, Card, GameStatus, Rules};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Directory holding the configuration, profiles and saved games, `$BLACKRUST_HOME` or `~/.blackrust`.
+pub(crate) fn data_dir() -> PathBuf {
+    match env::var_os("BLACKRUST_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join(".blackrust")
+    }
+}
+
+/// File of a player in `folder`, names that could point outside of it are refused.
+fn file_path(folder: &str, name: &str) -> Result<PathBuf, BlackjackError> {
+    if name.is_empty() || name.starts_with('.') || name.chars().any(|c| c == '/' || c == '\\' || c.is_control()) {
+        return Err(BlackjackError::Storage(format!("invalid player name {:?}", name)));
+    }
+    Ok(data_dir().join(folder).join(format!("{}.json", name)))
+}
+
+fn read_json<T: DeserializeOwned>(path: &PathBuf) -> Result<T, BlackjackError> {
+    let data: String = fs::read_to_string(path)
+        .map_err(|e| BlackjackError::Storage(format!("{}: {}", path.display(), e)))?;
+    serde_json::from_str(&data)
+        .map_err(|e| BlackjackError::Storage(format!("{}: {}", path.display(), e)))
+}
+
+fn write_json<T: Serialize>(path: &PathBuf, value: &T) -> Result<(), BlackjackError> {
+    let error = |e: String| BlackjackError::Storage(format!("{}: {}", path.display(), e));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| error(e.to_string()))?;
+    }
+    let data: String = serde_json::to_string_pretty(value).map_err(|e| error(e.to_string()))?;
+    fs::write(path, data).map_err(|e| error(e.to_string()))
+}
+
+/// Lifetime results of a player.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub rounds: u32,
+    pub wins: u32,
+    pub ties: u32,
+    pub losses: u32,
+    /// Won or lost over every session.
+    pub net: i64,
+    pub decisions: u32,
+    /// Decisions that matched basic strategy.
+    pub correct_decisions: u32,
+}
+
+impl Stats {
+    /// Counts the outcome of a hand.
+    pub fn record_result(&mut self, status: GameStatus) {
+        match status {
+            GameStatus::Win => self.wins += 1,
+            GameStatus::Tie => self.ties += 1,
+            GameStatus::Lose => self.losses += 1,
+        }
+    }
+
+    /// Counts a decision, `correct` when it followed the chart.
+    pub fn record_decision(&mut self, correct: bool) {
+        self.decisions += 1;
+        if correct {
+            self.correct_decisions += 1;
+        }
+    }
+
+    /// Share of decisions that followed basic strategy, from 0 to 1.
+    pub fn accuracy(&self) -> f64 {
+        if self.decisions == 0 {
+            0.0
+        } else {
+            self.correct_decisions as f64 / self.decisions as f64
+        }
+    }
+}
+
+/// A named player kept on disk between runs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// Money the player takes to the next session.
+    pub bankroll: u32,
+    pub stats: Stats,
+    /// Rules of the last session, used when no other rules are given.
+    pub rules: Rules,
+}
+
+impl Profile {
+    /// A new player with no money and the default rules.
+    pub fn new(name: &str) -> Self {
+        Profile {
+            name: name.to_string(),
+            bankroll: 0,
+            stats: Stats::default(),
+            rules: Rules::default(),
+        }
+    }
+
+    /// A profile is stored under `name`.
+    pub fn exists(name: &str) -> bool {
+        file_path("profiles", name).is_ok_and(|path| path.exists())
+    }
+
+    /// Reads the profile stored under `name`.
+    pub fn load(name: &str) -> Result<Self, BlackjackError> {
+        read_json(&file_path("profiles", name)?)
+    }
+
+    /// The profile stored under `name`, or a new one when there is none.
+    pub fn load_or_create(name: &str) -> Result<Self, BlackjackError> {
+        file_path("profiles", name)?;
+        if Profile::exists(name) {
+            Profile::load(name)
+        } else {
+            Ok(Profile::new(name))
+        }
+    }
+
+    /// Stores the profile under its name.
+    pub fn save(&self) -> Result<(), BlackjackError> {
+        write_json(&file_path("profiles", &self.name)?, self)
+    }
+}
+
+/// A game stopped between two rounds, with the shoe exactly as it was left.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub rules: Rules,
+    /// Cards left to deal, the next one last.
+    pub shoe: Vec<Card>,
+    pub discards: Vec<Card>,
+    /// Hi-Lo running count of the discard tray.
+    pub running_count: i32,
+    /// Seed of the shoe.
+    pub seed: u64,
+    /// Shuffles of the shoe so far.
+    pub shuffles: u64,
+    /// Cards left out of the shoe by a mid round reshuffle.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub held_back: Vec<Card>,
+    pub wallet: u32,
+    /// Main bet of the last round.
+    pub last_bet: u32,
+    pub ledger: Ledger,
+}
+
+impl SavedGame {
+    /// A game is saved under `name`.
+    pub fn exists(name: &str) -> bool {
+        file_path("saves", name).is_ok_and(|path| path.exists())
+    }
+
+    /// Reads the game saved under `name`.
+    pub fn load(name: &str) -> Result<Self, BlackjackError> {
+        read_json(&file_path("saves", name)?)
+    }
+
+    /// Saves the game under `name`, replacing any earlier save.
+    pub fn save(&self, name: &str) -> Result<(), BlackjackError> {
+        write_json(&file_path("saves", name)?, self)
+    }
+
+    /// Drops the saved game once it has been resumed.
+    pub fn remove(name: &str) -> Result<(), BlackjackError> {
+        let path: PathBuf = file_path("saves", name)?;
+        fs::remove_file(&path)
+            .map_err(|e| BlackjackError::Storage(format!("{}: {}", path.display(), e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Action, Round};
+
+    #[test]
+    fn names_that_leave_the_folder_are_refused() {
+        assert!(file_path("profiles", "alice").unwrap().ends_with("profiles/alice.json"));
+        for name in ["", "..", ".hidden", "a/b", "a\\b", "a\nb"] {
+            assert!(matches!(file_path("profiles", name), Err(BlackjackError::Storage(_))), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn accuracy_is_the_share_of_correct_decisions() {
+        let mut stats: Stats = Stats::default();
+        assert_eq!(stats.accuracy(), 0.0);
+        for correct in [true, true, false, true] {
+            stats.record_decision(correct);
+        }
+        assert_eq!(stats.accuracy(), 0.75);
+    }
+
+    #[test]
+    fn a_saved_game_resumes_with_the_same_shoe_and_wallet() {
+        let mut round: Round = Round::with_seed(Rules::default(), 5).unwrap();
+        round.money.deposit(100);
+        let json: String = serde_json::to_string(&round.save().unwrap()).unwrap();
+        let mut resumed: Round = Round::resume(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(resumed.money().wallet(), 100);
+        for round in [&mut round, &mut resumed] {
+            round.act(Action::Bet(10)).unwrap();
+            round.act(Action::Deal).unwrap();
+        }
+        assert_eq!(resumed.hands()[0].cards(), round.hands()[0].cards());
+        assert_eq!(resumed.dealer_hand().up_card(), round.dealer_hand().up_card());
+    }
+}
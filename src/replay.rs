@@ -0,0 +1,144 @@
+use crate::history::{read_history, RoundRecord};
+use crate::{Action, BlackjackError, Phase, Round};
+
+/// A recorded round played again through the engine.
+pub struct Replay {
+    /// Record produced by the replayed round.
+    pub record: RoundRecord,
+    /// Differences with what was recorded, empty when the engine agrees.
+    pub mismatches: Vec<String>,
+}
+
+impl Replay {
+    /// The engine played the round as it was recorded.
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Re-executes a recorded round from its shoe position and compares the outcome.
+pub fn replay_round(record: &RoundRecord) -> Result<Replay, BlackjackError> {
+    let mut round: Round = Round::from_record(record)?;
+    let mut mismatches: Vec<String> = Vec::new();
+    let mut decisions = record.decisions.iter();
+    let opening: Result<(), BlackjackError> = record
+        .opening_actions()
+        .into_iter()
+        .try_for_each(|action| round.act(action));
+    if let Err(error) = opening {
+        mismatches.push(error.to_string());
+    }
+    while mismatches.is_empty() && round.phase() != Phase::Cleanup {
+        let action: Action = match round.phase() {
+            Phase::Switch => Action::Switch(record.switched.unwrap_or(false)),
+            Phase::Insurance => Action::Insurance(record.insurance.unwrap_or(false)),
+            Phase::PlayerTurns => match decisions.next() {
+                Some(decision) => Action::Decide(decision.decision),
+                None => {
+                    mismatches.push("the hand needs more decisions than were recorded".to_string());
+                    break;
+                }
+            },
+            Phase::DealerTurn => Action::DealerPlay,
+            Phase::Settlement => Action::Settle,
+            _ => break
+        };
+        if let Err(error) = round.act(action) {
+            mismatches.push(error.to_string());
+        }
+    }
+    let mut replayed: RoundRecord = round.record().clone();
+    replayed.round = record.round;
+    if decisions.next().is_some() {
+        mismatches.push("recorded decisions were left over".to_string());
+    }
+    compare(&mut mismatches, "dealt cards", &record.dealt, &replayed.dealt);
+    compare(&mut mismatches, "side bets", &record.side_bets, &replayed.side_bets);
+    compare(&mut mismatches, "decisions", &record.decisions, &replayed.decisions);
+    compare(&mut mismatches, "player hands", &record.player_hands, &replayed.player_hands);
+    compare(&mut mismatches, "dealer hand", &record.dealer_hand, &replayed.dealer_hand);
+    compare(&mut mismatches, "results", &record.results, &replayed.results);
+    compare(&mut mismatches, "final wallet", &record.final_wallet, &replayed.final_wallet);
+    Ok(Replay { record: replayed, mismatches })
+}
+
+fn compare<T: PartialEq + std::fmt::Debug>(mismatches: &mut Vec<String>, what: &str, recorded: &T, replayed: &T) {
+    if recorded != replayed {
+        mismatches.push(format!("{} differ: recorded {:?}, replayed {:?}", what, recorded, replayed));
+    }
+}
+
+/// Replays every round of a history file.
+pub fn replay_file(path: &str) -> Result<Vec<Replay>, BlackjackError> {
+    read_history(path)?
+        .iter()
+        .map(replay_round)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Decision, Rules};
+
+    /// Plays `rounds` rounds hitting below 17, returning their records.
+    fn play(rules: Rules, seed: u64, rounds: u32) -> Vec<RoundRecord> {
+        let mut round: Round = Round::with_seed(rules, seed).unwrap();
+        round.money.deposit(1_000_000);
+        let mut records: Vec<RoundRecord> = Vec::new();
+        while records.len() < rounds as usize {
+            let action: Action = match round.phase() {
+                Phase::Betting => Action::Bet(10),
+                Phase::Dealing => Action::Deal,
+                Phase::Switch => Action::Switch(false),
+                Phase::Insurance => Action::Insurance(false),
+                Phase::PlayerTurns => match round.active_hand() {
+                    Some(hand) if hand.value().total < 17 => Action::Decide(Decision::Hit),
+                    _ => Action::Decide(Decision::Stand)
+                },
+                Phase::DealerTurn => Action::DealerPlay,
+                Phase::Settlement => Action::Settle,
+                Phase::Cleanup => Action::Cleanup,
+            };
+            round.act(action).unwrap();
+            if action == Action::Settle {
+                records.push(round.record().clone());
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn replays_rounds_dealt_after_a_mid_round_reshuffle() {
+        let rules: Rules = Rules { penetration: 1.0, ..Rules::default() };
+        let records: Vec<RoundRecord> = play(rules, 7, 300);
+        assert!(records.iter().any(|r| !r.held_back.is_empty()));
+        for record in records.iter() {
+            let replay: Replay = replay_round(record).unwrap();
+            assert!(replay.is_ok(), "round {}: {:?}", record.round, replay.mismatches);
+        }
+    }
+
+    #[test]
+    fn a_seeded_session_replays_round_for_round() {
+        for record in play(Rules::default(), 21, 50).iter() {
+            let replay: Replay = replay_round(record).unwrap();
+            assert!(replay.is_ok(), "round {}: {:?}", record.round, replay.mismatches);
+            assert_eq!(replay.record.dealt, record.dealt);
+        }
+    }
+
+    #[test]
+    fn a_changed_record_is_reported() {
+        let mut record: RoundRecord = play(Rules::default(), 21, 1).remove(0);
+        record.final_wallet += 5;
+        let replay: Replay = replay_round(&record).unwrap();
+        assert!(!replay.is_ok());
+        assert!(replay.mismatches[0].starts_with("final wallet differ"));
+
+        let mut record: RoundRecord = play(Rules::default(), 21, 1).remove(0);
+        record.decisions.clear();
+        let replay: Replay = replay_round(&record).unwrap();
+        assert!(replay.mismatches.iter().any(|m| m.contains("needs more decisions")), "{:?}", replay.mismatches);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::history::RoundRecord;
+use crate::ledger::Ledger;
+use crate::{Decision, GameStatus, HandValue, Rules, SideBetKind};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Tables of the CSV output, one per file so any CSV reader can load it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CsvTable {
+    /// A row per simulated round.
+    #[default]
+    Rounds,
+    /// A single row with the rules and the totals.
+    Summary,
+    /// A row per side bet placed.
+    SideBets,
+}
+
+/// One simulated round, a row of the CSV output.
+#[derive(Clone, Debug, Serialize)]
+pub struct RoundRow {
+    pub round: u32,
+    pub shuffle: u64,
+    pub bet: u32,
+    /// Hands played, splits included.
+    pub hands: usize,
+    /// Everything put on the table, doubles, splits and insurance included.
+    pub wagered: u32,
+    /// Everything given back, stakes included.
+    pub payout: u32,
+    /// Payout less wagered.
+    pub net: i64,
+    /// Net result of the side bets, kept out of `net`.
+    pub side_net: i64,
+    /// Wallet after the round.
+    pub wallet: u32,
+    /// Outcome of every hand, separated by `;`.
+    pub results: String,
+}
+
+/// Totals of one side bet over a simulation.
+#[derive(Clone, Debug, Serialize)]
+pub struct SideBetSummary {
+    pub kind: SideBetKind,
+    pub bets: u32,
+    /// Bets that paid something.
+    pub hits: u32,
+    pub wagered: u64,
+    pub payout: u64,
+    pub net: i64,
+    /// Net result per unit wagered.
+    pub edge: f64,
+    /// Share of the bets that paid.
+    pub hit_frequency: f64,
+}
+
+/// Totals over a whole simulation.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Summary {
+    pub strategy: String,
+    pub seed: u64,
+    pub rules: Rules,
+    pub rounds: u32,
+    pub hands: u32,
+    pub wins: u32,
+    pub ties: u32,
+    pub losses: u32,
+    pub blackjacks: u32,
+    pub doubles: u32,
+    pub splits: u32,
+    pub surrenders: u32,
+    pub switches: u32,
+    pub insurances: u32,
+    /// Bets placed before any double or split.
+    pub initial_bets: u64,
+    pub wagered: u64,
+    /// Free bets the house put up on free doubles and splits.
+    pub free_bets: u64,
+    pub payout: u64,
+    pub net: i64,
+    /// Net result per unit of initial bet.
+    pub edge: f64,
+    /// Net result of an average round.
+    pub mean_net: f64,
+    /// Standard deviation of the net result of a round.
+    pub std_dev: f64,
+    pub starting_wallet: u32,
+    pub final_wallet: u32,
+    pub peak_bankroll: u32,
+    pub max_drawdown: u32,
+    /// Side bets, which the figures above leave out apart from the wallets.
+    pub side_bets: Vec<SideBetSummary>,
+}
+
+/// Results of `Game::simulate`, written as text, JSON or CSV.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Simulation {
+    /// Totals of the simulation.
+    pub summary: Summary,
+    /// A row per round played.
+    pub rounds: Vec<RoundRow>,
+    #[serde(skip)]
+    squares: f64,
+}
+
+impl Simulation {
+    /// A simulation of `strategy` starting from `wallet`, before any round.
+    pub fn new(strategy: &str, seed: u64, rules: Rules, wallet: u32) -> Self {
+        Simulation {
+            summary: Summary {
+                strategy: strategy.to_string(),
+                seed,
+                rules,
+                starting_wallet: wallet,
+                final_wallet: wallet,
+                ..Summary::default()
+            },
+            ..Simulation::default()
+        }
+    }
+
+    /// Adds a settled round.
+    pub fn add(&mut self, record: &RoundRecord) {
+        let summary: &mut Summary = &mut self.summary;
+        let insurance: u32 = record.insurance_cost;
+        let wagered: u32 = record.results.iter().map(|r| r.wager).sum::<u32>() + insurance;
+        let mut side_net: i64 = 0;
+        for bet in record.side_bets.iter() {
+            let position: usize = match summary.side_bets.iter().position(|s| s.kind == bet.kind) {
+                Some(position) => position,
+                None => {
+                    summary.side_bets.push(SideBetSummary {
+                        kind: bet.kind,
+                        bets: 0,
+                        hits: 0,
+                        wagered: 0,
+                        payout: 0,
+                        net: 0,
+                        edge: 0.0,
+                        hit_frequency: 0.0,
+                    });
+                    summary.side_bets.len() - 1
+                }
+            };
+            let side: &mut SideBetSummary = &mut summary.side_bets[position];
+            side.bets += 1;
+            side.hits += (bet.payout > 0) as u32;
+            side.wagered += bet.wager as u64;
+            side.payout += bet.payout as u64;
+            side.net += bet.payout as i64 - bet.wager as i64;
+            side_net += bet.payout as i64 - bet.wager as i64;
+        }
+        let net: i64 = record.final_wallet as i64 - record.wallet as i64 - side_net;
+        let payout: u32 = (wagered as i64 + net) as u32;
+        let mut results: Vec<&str> = Vec::new();
+        for result in record.results.iter() {
+            match result.status {
+                GameStatus::Win => {
+                    summary.wins += 1;
+                    results.push("win");
+                },
+                GameStatus::Tie => {
+                    summary.ties += 1;
+                    results.push("tie");
+                },
+                GameStatus::Lose => {
+                    summary.losses += 1;
+                    results.push("lose");
+                }
+            }
+        }
+        let base_hands: usize = record.rules.base_hands();
+        if record.player_hands.len() == base_hands {
+            // Switching the second cards leaves no natural behind.
+            let switched: bool = record.switched == Some(true);
+            summary.blackjacks += record.player_hands.iter().filter(|h| HandValue::new(h, switched).blackjack).count() as u32;
+        }
+        summary.doubles += record.decisions.iter().filter(|d| d.decision == Decision::Double).count() as u32;
+        summary.splits += record.decisions.iter().filter(|d| d.decision == Decision::Split).count() as u32;
+        summary.surrenders += record.decisions.iter().filter(|d| d.decision == Decision::Surrender).count() as u32;
+        summary.switches += (record.switched == Some(true)) as u32;
+        summary.insurances += (insurance > 0) as u32;
+        summary.rounds += 1;
+        summary.hands += record.results.len() as u32;
+        summary.initial_bets += record.bet as u64 * base_hands as u64;
+        summary.wagered += wagered as u64;
+        summary.free_bets += record.results.iter().map(|r| r.free as u64).sum::<u64>();
+        summary.payout += payout as u64;
+        summary.net += net;
+        summary.final_wallet = record.final_wallet;
+        self.squares += (net * net) as f64;
+        self.rounds.push(RoundRow {
+            round: record.round,
+            shuffle: record.shuffle,
+            bet: record.bet,
+            hands: record.results.len(),
+            wagered,
+            payout,
+            net,
+            side_net,
+            wallet: record.final_wallet,
+            results: results.join(";"),
+        });
+    }
+
+    /// Works out the averages and the bankroll figures once every round is in.
+    pub fn finish(&mut self, ledger: &Ledger) {
+        let summary: &mut Summary = &mut self.summary;
+        if summary.rounds > 0 {
+            let rounds: f64 = summary.rounds as f64;
+            summary.mean_net = summary.net as f64 / rounds;
+            summary.std_dev = (self.squares / rounds - summary.mean_net * summary.mean_net).max(0.0).sqrt();
+        }
+        if summary.initial_bets > 0 {
+            summary.edge = summary.net as f64 / summary.initial_bets as f64;
+        }
+        for side in summary.side_bets.iter_mut() {
+            side.edge = side.net as f64 / side.wagered as f64;
+            side.hit_frequency = side.hits as f64 / side.bets as f64;
+        }
+        summary.peak_bankroll = ledger.peak_bankroll();
+        summary.max_drawdown = ledger.max_drawdown();
+    }
+
+    /// Summary and rounds as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// One of the tables as CSV, a header line and its rows.
+    pub fn to_csv(&self, table: CsvTable) -> String {
+        match table {
+            CsvTable::Rounds => self.rounds_csv(),
+            CsvTable::Summary => self.summary_csv(),
+            CsvTable::SideBets => self.side_bets_csv(),
+        }
+    }
+
+    fn rounds_csv(&self) -> String {
+        let mut csv: String = String::from("round,shuffle,bet,hands,wagered,payout,net,side_net,wallet,results\r\n");
+        for row in self.rounds.iter() {
+            csv.push_str(&csv_row(&[
+                row.round.to_string(), row.shuffle.to_string(), row.bet.to_string(), row.hands.to_string(),
+                row.wagered.to_string(), row.payout.to_string(), row.net.to_string(), row.side_net.to_string(),
+                row.wallet.to_string(), row.results.clone(),
+            ]));
+        }
+        csv
+    }
+
+    /// The strategy and seed, a column per rules field, then the totals.
+    fn summary_csv(&self) -> String {
+        let s: &Summary = &self.summary;
+        let mut columns: Vec<(String, String)> = vec![
+            ("strategy".to_string(), s.strategy.clone()),
+            ("seed".to_string(), s.seed.to_string()),
+        ];
+        flatten("", &serde_json::to_value(s.rules).unwrap_or_default(), &mut columns);
+        let totals: [(&str, String); 23] = [
+            ("rounds", s.rounds.to_string()),
+            ("hands", s.hands.to_string()),
+            ("wins", s.wins.to_string()),
+            ("ties", s.ties.to_string()),
+            ("losses", s.losses.to_string()),
+            ("blackjacks", s.blackjacks.to_string()),
+            ("doubles", s.doubles.to_string()),
+            ("splits", s.splits.to_string()),
+            ("surrenders", s.surrenders.to_string()),
+            ("switches", s.switches.to_string()),
+            ("insurances", s.insurances.to_string()),
+            ("initial_bets", s.initial_bets.to_string()),
+            ("wagered", s.wagered.to_string()),
+            ("free_bets", s.free_bets.to_string()),
+            ("payout", s.payout.to_string()),
+            ("net", s.net.to_string()),
+            ("edge", format!("{:.6}", s.edge)),
+            ("mean_net", format!("{:.4}", s.mean_net)),
+            ("std_dev", format!("{:.4}", s.std_dev)),
+            ("starting_wallet", s.starting_wallet.to_string()),
+            ("final_wallet", s.final_wallet.to_string()),
+            ("peak_bankroll", s.peak_bankroll.to_string()),
+            ("max_drawdown", s.max_drawdown.to_string()),
+        ];
+        columns.extend(totals.into_iter().map(|(name, value)| (name.to_string(), value)));
+        let (names, values): (Vec<String>, Vec<String>) = columns.into_iter().unzip();
+        csv_row(&names) + &csv_row(&values)
+    }
+
+    fn side_bets_csv(&self) -> String {
+        let mut csv: String = String::from("side_bet,bets,hits,wagered,payout,net,edge,hit_frequency\r\n");
+        for side in self.summary.side_bets.iter() {
+            csv.push_str(&csv_row(&[
+                side.kind.id().to_string(), side.bets.to_string(), side.hits.to_string(), side.wagered.to_string(),
+                side.payout.to_string(), side.net.to_string(), format!("{:.6}", side.edge),
+                format!("{:.6}", side.hit_frequency),
+            ]));
+        }
+        csv
+    }
+}
+
+/// Columns for every field of a serialized value, nested fields named by their path.
+fn flatten(prefix: &str, value: &Value, columns: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                let name: String = if prefix.is_empty() { name.clone() } else { format!("{}.{}", prefix, name) };
+                flatten(&name, field, columns);
+            }
+        },
+        Value::String(text) => columns.push((prefix.to_string(), text.clone())),
+        value => columns.push((prefix.to_string(), value.to_string())),
+    }
+}
+
+/// A CSV line, fields quoted as RFC 4180 asks when they hold a comma, a quote or a line break.
+fn csv_row(fields: &[String]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\r', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    fields.join(",") + "\r\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HandResult, Variant};
+
+    /// Switch round of 10 on each hand, insured for 10 and lost to a dealer blackjack.
+    fn insured_switch_round() -> RoundRecord {
+        let lost: HandResult = HandResult { status: GameStatus::Lose, wager: 10, free: 0, payout: 0 };
+        RoundRecord {
+            round: 1,
+            rules: Rules { variant: Variant::Switch, ..Rules::default() },
+            wallet: 1000,
+            bet: 10,
+            insurance: Some(true),
+            insurance_cost: 10,
+            results: vec![lost, lost],
+            final_wallet: 1000,
+            ..RoundRecord::default()
+        }
+    }
+
+    #[test]
+    fn insurance_counts_what_was_charged_for_every_hand() {
+        let mut simulation: Simulation = Simulation::new("basic", 0, Rules::default(), 1000);
+        simulation.add(&insured_switch_round());
+        assert_eq!(simulation.summary.insurances, 1);
+        assert_eq!(simulation.summary.wagered, 30);
+        assert_eq!(simulation.summary.payout, 30);
+        assert_eq!(simulation.summary.net, 0);
+    }
+
+    #[test]
+    fn csv_tables_have_one_header_each() {
+        let mut simulation: Simulation = Simulation::new("basic", 0, Rules::default(), 1000);
+        simulation.add(&insured_switch_round());
+        simulation.add(&insured_switch_round());
+        for (table, rows) in [(CsvTable::Rounds, 3), (CsvTable::Summary, 2), (CsvTable::SideBets, 1)] {
+            let csv: String = simulation.to_csv(table);
+            assert_eq!(csv.lines().count(), rows, "{:?}", table);
+            assert!(csv.lines().all(|line| !line.is_empty()));
+        }
+    }
+
+    #[test]
+    fn summary_csv_has_a_column_per_rules_field() {
+        let rules: Rules = Rules { blackjack_pays: crate::BlackjackPayout::SixToFive, ..Rules::default() };
+        let csv: String = Simulation::new("basic", 0, rules, 1000).to_csv(CsvTable::Summary);
+        let rows: Vec<&str> = csv.lines().collect();
+        let header: Vec<&str> = rows[0].split(',').collect();
+        let values: Vec<&str> = rows[1].split(',').collect();
+        assert_eq!(header.len(), values.len());
+        let column = |name: &str| values[header.iter().position(|h| *h == name).unwrap()];
+        assert_eq!(column("blackjack_pays"), "6:5");
+        assert_eq!(column("peek"), "hole-card");
+        assert_eq!(column("side_bets.perfect_pairs.perfect"), "25");
+    }
+
+    #[test]
+    fn csv_fields_are_quoted() {
+        assert_eq!(csv_row(&["a,b".to_string(), "say \"hi\"".to_string(), "plain".to_string()]), "\"a,b\",\"say \"\"hi\"\"\",plain\r\n");
+        let csv: String = Simulation::new("charts/a,b.txt", 0, Rules::default(), 1000).to_csv(CsvTable::Summary);
+        assert!(csv.lines().nth(1).unwrap().starts_with("\"charts/a,b.txt\",0,"));
+    }
+}
@@ -0,0 +1,1265 @@
+use crate::card::Card;
+use crate::error::BlackjackError;
+use crate::hand::{DealerHand, Hand, HandValue, FIVE_CARD_TRICK};
+use crate::history::{DecisionRecord, RoundRecord};
+use crate::ledger::Transaction;
+use crate::money::Money;
+use crate::profile::SavedGame;
+use crate::rules::{Rules, Variant};
+use crate::shoe::Deck;
+use crate::side_bet::{SideBetKind, SideBetResult, SideBetTiming};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a hand for the player.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameStatus {
+    Win,
+    Tie,
+    Lose,
+}
+
+/// What the player does with a hand on their turn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Decision {
+    Stand,
+    Hit,
+    Double,
+    Split,
+    /// Gives up the hand for half the wager, or rescues a doubled hand for the double.
+    Surrender,
+    /// No decision, the hand is left as it is.
+    None,
+}
+
+/// Most hands a player can hold after splitting.
+const MAX_HANDS: usize = 4;
+
+/// Steps of a round, in the order they are played.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Phase {
+    /// Waiting for the main bet.
+    #[default]
+    Betting,
+    /// Side bets may be placed before the cards are dealt.
+    Dealing,
+    /// Blackjack Switch only: the player may swap the second cards of their two hands.
+    Switch,
+    /// Insurance is offered against a dealer ace.
+    Insurance,
+    /// The player's hands are played one after the other.
+    PlayerTurns,
+    /// The dealer reveals the hole card and draws.
+    DealerTurn,
+    /// Hands and side bets are paid.
+    Settlement,
+    /// The cards go to the discard tray before the next bet.
+    Cleanup,
+}
+
+/// Moves that take a [`Round`] forward, given to [`Round::act`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Wager for the next round. As a legal action it carries the minimum bet.
+    Bet(u32),
+    /// Side bet placed after the main bet, before the deal.
+    /// As a legal action it carries the smallest amount.
+    SideBet(SideBetKind, u32),
+    /// Deals the opening cards.
+    Deal,
+    /// Whether to swap the second cards of the two hands.
+    Switch(bool),
+    /// Takes or declines insurance.
+    Insurance(bool),
+    /// Plays a decision on the active hand.
+    Decide(Decision),
+    /// Plays the dealer's hand.
+    DealerPlay,
+    /// Pays every hand and the remaining side bets.
+    Settle,
+    /// Clears the table for the next round, reshuffling at the cut card.
+    Cleanup,
+}
+
+/// Outcome of one of the player's hands.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandResult {
+    pub status: GameStatus,
+    /// The player's stake on the hand.
+    pub wager: u32,
+    /// Free bet on the hand, staked by the house.
+    #[serde(default)]
+    pub free: u32,
+    /// Amount given back to the player, stake included.
+    pub payout: u32,
+}
+
+/// A seat at the table played one round at a time through its phases.
+#[derive(Clone, Default)]
+pub struct Round {
+    pub(crate) deck: Deck,
+    pub(crate) rules: Rules,
+    pub(crate) money: Money,
+    pub(crate) phase: Phase,
+    pub(crate) hands: Vec<Hand>,
+    pub(crate) active: usize,
+    pub(crate) dealer_hand: DealerHand,
+    pub(crate) insurance: u32,
+    pub(crate) side_bets: Vec<SideBetResult>,
+    pub(crate) results: Vec<HandResult>,
+    pub(crate) record: RoundRecord,
+}
+
+impl Round {
+    /// Round at a table with `rules`, the shoe shuffled from a random seed.
+    pub fn new(rules: Rules) -> Result<Self, BlackjackError> {
+        Round::with_seed(rules, rand::thread_rng().gen())
+    }
+
+    /// Round whose shoe is shuffled from `seed`.
+    pub fn with_seed(rules: Rules, seed: u64) -> Result<Self, BlackjackError> {
+        rules.validate()?;
+        Ok(Round {
+            deck: Deck::new(&rules, seed),
+            rules,
+            ..Round::default()
+        })
+    }
+
+    /// Picks a game back up where it was saved.
+    pub fn resume(saved: SavedGame) -> Result<Self, BlackjackError> {
+        saved.rules.validate()?;
+        let size: usize = saved.shoe.len() + saved.discards.len();
+        if size != saved.rules.decks as usize * saved.rules.deck_size() {
+            return Err(BlackjackError::InvalidConfig(
+                format!("saved shoe has {} cards for {} decks", size, saved.rules.decks)
+            ));
+        }
+        Ok(Round {
+            deck: Deck {
+                cards: saved.shoe,
+                discards: saved.discards,
+                size,
+                deck_size: saved.rules.deck_size(),
+                penetration: saved.rules.penetration,
+                seed: saved.seed,
+                shuffles: saved.shuffles,
+                held_back: saved.held_back,
+            },
+            rules: saved.rules,
+            money: Money {
+                wallet: saved.wallet,
+                last_bet: saved.last_bet,
+                ledger: saved.ledger,
+            },
+            ..Round::default()
+        })
+    }
+
+    /// Round set up as it was right before `record` was played.
+    pub fn from_record(record: &RoundRecord) -> Result<Self, BlackjackError> {
+        record.rules.validate()?;
+        let deck: Deck = Deck::at(&record.rules, record.seed, record.shuffle, record.shoe_position, &record.held_back);
+        let mut money: Money = Money::default();
+        money.deposit(record.wallet);
+        Ok(Round { deck, rules: record.rules, money, ..Round::default() })
+    }
+
+    /// Snapshot of the game, only taken between rounds.
+    pub fn save(&self) -> Result<SavedGame, BlackjackError> {
+        if self.phase != Phase::Betting {
+            return Err(BlackjackError::Storage("games can only be saved between rounds".to_string()));
+        }
+        Ok(SavedGame {
+            rules: self.rules,
+            shoe: self.deck.cards.clone(),
+            discards: self.deck.discards.clone(),
+            running_count: self.deck.running_count(),
+            seed: self.deck.seed,
+            shuffles: self.deck.shuffles,
+            held_back: self.deck.held_back.clone(),
+            wallet: self.money.wallet,
+            last_bet: self.money.last_bet,
+            ledger: self.money.ledger.clone(),
+        })
+    }
+
+    /// The shoe and its discard tray.
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
+    /// Cards the player hasn't seen: the rest of the shoe and the dealer's hole card.
+    pub fn unseen_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.deck.cards.clone();
+        cards.extend(self.dealer_hand.cards.iter().skip(self.dealer_hand.visible_cards().len()));
+        cards
+    }
+
+    /// Phase the round is waiting in.
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Rules of the table.
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// The player's wallet and ledger.
+    pub fn money(&self) -> &Money {
+        &self.money
+    }
+
+    /// The player's hands, in the order they are played.
+    pub fn hands(&self) -> &[Hand] {
+        &self.hands
+    }
+
+    /// Hand waiting for a decision during the player turns.
+    pub fn active_hand(&self) -> Option<&Hand> {
+        if self.phase == Phase::PlayerTurns {
+            self.hands.get(self.active)
+        } else {
+            None
+        }
+    }
+
+    /// The dealer's hand, the hole card hidden until revealed.
+    pub fn dealer_hand(&self) -> &DealerHand {
+        &self.dealer_hand
+    }
+
+    /// Insurance taken this round, 0 when declined.
+    pub fn insurance(&self) -> u32 {
+        self.insurance
+    }
+
+    /// Side bets of the round, paid as soon as they are decided.
+    pub fn side_bets(&self) -> &[SideBetResult] {
+        &self.side_bets
+    }
+
+    /// Results of the last settlement.
+    pub fn results(&self) -> &[HandResult] {
+        &self.results
+    }
+
+    /// History of the round being played, complete once settled.
+    pub fn record(&self) -> &RoundRecord {
+        &self.record
+    }
+
+    /// Actions [`Round::act`] takes in the current phase, empty when the player can't cover the minimum bet.
+    /// Bets and side bets carry their smallest amount.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        match self.phase {
+            Phase::Betting => {
+                if self.money.wallet >= self.rules.min_bet * self.rules.base_hands() as u32 {
+                    vec![Action::Bet(self.rules.min_bet)]
+                } else {
+                    Vec::new()
+                }
+            },
+            Phase::Dealing => {
+                let mut actions: Vec<Action> = vec![Action::Deal];
+                if self.money.wallet > 0 {
+                    actions.extend(SideBetKind::ALL
+                        .into_iter()
+                        .filter(|kind| self.side_bets.iter().all(|b| b.kind != *kind))
+                        .map(|kind| Action::SideBet(kind, 1)));
+                }
+                actions
+            },
+            Phase::Switch => vec![Action::Switch(false), Action::Switch(true)],
+            Phase::Insurance => {
+                let mut actions: Vec<Action> = vec![Action::Insurance(false)];
+                let cost: u32 = self.insurance_cost();
+                if cost > 0 && cost <= self.money.wallet {
+                    actions.push(Action::Insurance(true));
+                }
+                actions
+            },
+            Phase::PlayerTurns => self.legal_decisions()
+                .into_iter()
+                .map(Action::Decide)
+                .collect(),
+            Phase::DealerTurn => vec![Action::DealerPlay],
+            Phase::Settlement => vec![Action::Settle],
+            Phase::Cleanup => vec![Action::Cleanup],
+        }
+    }
+
+    /// Decisions open to the active hand, empty outside the player turns.
+    pub fn legal_decisions(&self) -> Vec<Decision> {
+        let mut decisions: Vec<Decision> = Vec::new();
+        if let Some(hand) = self.active_hand() {
+            if hand.doubled {
+                // Only reachable with double-down rescue, the hand takes no more cards.
+                return vec![Decision::Stand, Decision::Surrender];
+            }
+            let pontoon: bool = self.rules.variant == Variant::Pontoon;
+            decisions.push(Decision::Hit);
+            // Pontoon doesn't let the player stick under 15.
+            if !pontoon || hand.value().total >= 15 {
+                decisions.push(Decision::Stand);
+            }
+            let affordable: bool = hand.action() <= self.money.wallet;
+            let first_two: bool = hand.cards.len() == 2;
+            if pontoon {
+                // Cards can be bought until the first twist, each for the opening stake.
+                let buying: bool = hand.bought as usize + 2 == hand.cards.len();
+                if buying && self.buy_stake(hand) <= self.money.wallet {
+                    decisions.push(Decision::Double);
+                }
+            } else if (affordable || self.free_double(hand)) && (first_two || self.rules.double_any_cards()) {
+                decisions.push(Decision::Double);
+            }
+            let max_hands: usize = MAX_HANDS * self.rules.base_hands();
+            let can_split: bool = affordable || self.free_split(hand);
+            if first_two && can_split && hand.value().pair.is_some() && self.hands.len() < max_hands {
+                decisions.push(Decision::Split);
+            }
+            if first_two && !hand.split && self.rules.allows_surrender() {
+                decisions.push(Decision::Surrender);
+            }
+        }
+        decisions
+    }
+
+    /// Pontoon: what buying one more card costs.
+    fn buy_stake(&self, hand: &Hand) -> u32 {
+        hand.bet / (hand.bought as u32 + 1)
+    }
+
+    /// No more cards go to a hand on 21 or more, or on a five card trick.
+    fn is_complete(&self, hand: &Hand) -> bool {
+        hand.value().total >= 21 || (self.rules.five_card_trick() && hand.cards.len() >= 5)
+    }
+
+    /// Free Bet: doubling a two card hard 9, 10 or 11 is on the house.
+    pub fn free_double(&self, hand: &Hand) -> bool {
+        let value: HandValue = hand.value();
+        self.rules.free_bets() && hand.cards.len() == 2 && value.is_hard() && (9..=11).contains(&value.total)
+    }
+
+    /// Free Bet: splitting any pair but tens is on the house.
+    pub fn free_split(&self, hand: &Hand) -> bool {
+        self.rules.free_bets() && hand.value().pair.is_some() && hand.cards[0].value != 10
+    }
+
+    /// Whether [`Round::act`] takes `action` now, any amount being legal for a bet at this point.
+    pub fn is_legal(&self, action: Action) -> bool {
+        match action {
+            Action::Bet(_) => self.phase == Phase::Betting,
+            Action::SideBet(kind, _) => self.phase == Phase::Dealing && self.side_bets.iter().all(|b| b.kind != kind),
+            _ => self.legal_actions().contains(&action),
+        }
+    }
+
+    /// Plays `action` and moves to the next phase once the current one is done.
+    /// An action that isn't legal is refused with [`BlackjackError::IllegalAction`], leaving the round as it was.
+    pub fn act(&mut self, action: Action) -> Result<(), BlackjackError> {
+        if !self.is_legal(action) {
+            return Err(BlackjackError::IllegalAction(self.phase, action));
+        }
+        match action {
+            Action::Bet(amount) => self.bet(amount),
+            Action::SideBet(kind, amount) => self.side_bet(kind, amount),
+            Action::Deal => self.deal(),
+            Action::Switch(switch) => self.switch(switch),
+            Action::Insurance(take) => self.take_insurance(take),
+            Action::Decide(decision) => self.decide(decision),
+            Action::DealerPlay => self.dealer_play(),
+            Action::Settle => {
+                self.settle();
+                Ok(())
+            },
+            Action::Cleanup => {
+                self.cleanup();
+                Ok(())
+            },
+        }
+    }
+
+    /// Checks a wager against the table limits and the player's wallet.
+    /// In Blackjack Switch the wager goes on each of the two hands.
+    pub fn validate_bet(&self, amount: u32) -> Result<(), BlackjackError> {
+        if amount < self.rules.min_bet || amount > self.rules.max_bet {
+            return Err(BlackjackError::InvalidBet(amount));
+        }
+        let needed: u32 = amount * self.rules.base_hands() as u32;
+        if needed > self.money.wallet {
+            return Err(BlackjackError::InsufficientFunds { needed, available: self.money.wallet });
+        }
+        Ok(())
+    }
+
+    fn bet(&mut self, amount: u32) -> Result<(), BlackjackError> {
+        self.validate_bet(amount)?;
+        self.record = RoundRecord {
+            round: self.money.ledger.round() + 1,
+            seed: self.deck.seed,
+            shuffle: self.deck.shuffles,
+            shoe_position: self.deck.position(),
+            held_back: self.deck.held_back.clone(),
+            rules: self.rules,
+            wallet: self.money.wallet,
+            bet: amount,
+            ..RoundRecord::default()
+        };
+        self.money.ledger.start_round();
+        self.hands.clear();
+        for _ in 0..self.rules.base_hands() {
+            self.money.debit(amount, Transaction::Bet)?;
+            self.hands.push(Hand { bet: amount, ..Hand::default() });
+        }
+        self.money.last_bet = amount;
+        self.phase = Phase::Dealing;
+        Ok(())
+    }
+
+    fn side_bet(&mut self, kind: SideBetKind, amount: u32) -> Result<(), BlackjackError> {
+        if amount == 0 || amount > self.rules.max_bet {
+            return Err(BlackjackError::InvalidBet(amount));
+        }
+        self.money.debit(amount, Transaction::SideBet)?;
+        self.side_bets.push(SideBetResult { kind, wager: amount, payout: 0 });
+        Ok(())
+    }
+
+    /// The player's first two cards as dealt, before any switch or split.
+    fn opening_cards(&self) -> [Card; 2] {
+        let dealt: &[Card] = &self.record.dealt;
+        [dealt[0], dealt[self.rules.base_hands() + 1]]
+    }
+
+    /// When the `kind` side bet is decided at this table.
+    pub fn side_bet_timing(&self, kind: SideBetKind) -> SideBetTiming {
+        self.rules.side_bets.get(kind).timing(&self.rules)
+    }
+
+    /// A side bet still waits for the dealer's finished hand.
+    fn dealer_side_bets(&self) -> bool {
+        self.side_bets.iter().any(|b| self.side_bet_timing(b.kind) == SideBetTiming::DealerTurn)
+    }
+
+    /// Pays the side bets decided at `timing`.
+    fn settle_side_bets(&mut self, timing: SideBetTiming) {
+        let player: [Card; 2] = self.opening_cards();
+        for i in 0..self.side_bets.len() {
+            let bet: SideBetResult = self.side_bets[i];
+            if self.side_bet_timing(bet.kind) != timing {
+                continue;
+            }
+            let odds: Option<u32> = self.rules.side_bets.get(bet.kind).odds(&player, &self.dealer_hand.cards);
+            let payout: u32 = odds.map_or(0, |odds| bet.wager + bet.wager * odds);
+            self.side_bets[i].payout = payout;
+            self.money.credit(payout, Transaction::SideBetPayout);
+        }
+        self.record.side_bets = self.side_bets.clone();
+    }
+
+    fn deal(&mut self) -> Result<(), BlackjackError> {
+        for i in 0..2 {
+            for hand in 0..self.hands.len() {
+                let card: Card = self.draw()?;
+                self.hands[hand].add_card(card);
+            }
+            if i == 0 || self.rules.has_hole_card() {
+                let card: Card = self.draw()?;
+                self.dealer_hand.add_card(card);
+            }
+        }
+        if self.rules.dealer_exposed() {
+            self.dealer_hand.reveal();
+        }
+        self.dealer_hand.face_down = self.rules.dealer_face_down();
+        self.settle_side_bets(SideBetTiming::Deal);
+        if self.rules.variant == Variant::Switch {
+            self.phase = Phase::Switch;
+            Ok(())
+        } else {
+            self.offer_insurance()
+        }
+    }
+
+    /// Swaps the second cards of the two Blackjack Switch hands.
+    fn switch(&mut self, switch: bool) -> Result<(), BlackjackError> {
+        self.record.switched = Some(switch);
+        if switch {
+            let second: Card = self.hands[1].cards[1];
+            self.hands[1].cards[1] = self.hands[0].cards[1];
+            self.hands[0].cards[1] = second;
+            for hand in self.hands.iter_mut() {
+                hand.switched = true;
+            }
+        }
+        self.offer_insurance()
+    }
+
+    fn offer_insurance(&mut self) -> Result<(), BlackjackError> {
+        // There's nothing to insure against when the hole card is showing.
+        if !self.rules.dealer_exposed() && self.dealer_hand.visible_cards().first().is_some_and(|c| c.rank == 'A') {
+            self.phase = Phase::Insurance;
+            Ok(())
+        } else {
+            self.check_dealer()
+        }
+    }
+
+    /// Insurance is half of what the hands carry.
+    fn insurance_cost(&self) -> u32 {
+        self.hands.iter().map(|h| h.bet).sum::<u32>() / 2
+    }
+
+    /// Takes the next card from the shoe, keeping track of it for the history.
+    fn draw(&mut self) -> Result<Card, BlackjackError> {
+        let card: Card = self.deck.hit()?;
+        self.record.dealt.push(card);
+        Ok(card)
+    }
+
+    fn take_insurance(&mut self, take: bool) -> Result<(), BlackjackError> {
+        self.record.insurance = Some(take);
+        if take {
+            let cost: u32 = self.insurance_cost();
+            self.money.debit(cost, Transaction::Insurance)?;
+            self.insurance = cost;
+            self.record.insurance_cost = cost;
+        }
+        self.check_dealer()
+    }
+
+    /// Ends the round right away if the dealer peeks a blackjack.
+    fn check_dealer(&mut self) -> Result<(), BlackjackError> {
+        if self.dealer_hand.peek(&self.rules) {
+            self.dealer_hand.reveal();
+            self.phase = Phase::Settlement;
+            Ok(())
+        } else {
+            self.phase = Phase::PlayerTurns;
+            self.active = 0;
+            self.start_hand()
+        }
+    }
+
+    /// Readies the active hand, moving on when it needs no decision.
+    fn start_hand(&mut self) -> Result<(), BlackjackError> {
+        while self.active < self.hands.len() {
+            if self.hands[self.active].cards.len() < 2 {
+                let card: Card = self.draw()?;
+                self.hands[self.active].add_card(card);
+            }
+            let hand: &mut Hand = &mut self.hands[self.active];
+            let split_aces: bool = hand.split && hand.cards[0].rank == 'A';
+            if split_aces || hand.value().total >= 21 {
+                hand.finished = true;
+                self.active += 1;
+            } else {
+                return Ok(());
+            }
+        }
+        self.end_player_turns();
+        Ok(())
+    }
+
+    fn end_player_turns(&mut self) {
+        // Without a hole card the dealer still draws one to settle the insurance.
+        let insured: bool = self.insurance > 0 && !self.rules.has_hole_card();
+        if self.hands.iter().all(|h| h.value().bust || h.surrendered) && !insured && !self.dealer_side_bets() {
+            self.dealer_hand.reveal();
+            self.phase = Phase::Settlement;
+        } else {
+            self.phase = Phase::DealerTurn;
+        }
+    }
+
+    fn finish_hand(&mut self) -> Result<(), BlackjackError> {
+        self.hands[self.active].finished = true;
+        self.active += 1;
+        self.start_hand()
+    }
+
+    fn decide(&mut self, decision: Decision) -> Result<(), BlackjackError> {
+        self.record.decisions.push(DecisionRecord {
+            hand: self.active,
+            cards: self.hands[self.active].cards.clone(),
+            up_card: self.dealer_hand.visible_cards().first().copied(),
+            decision,
+            legal: self.legal_decisions(),
+        });
+        match decision {
+            Decision::Hit => {
+                let card: Card = self.draw()?;
+                self.hands[self.active].add_card(card);
+                if self.is_complete(&self.hands[self.active]) {
+                    self.finish_hand()
+                } else {
+                    Ok(())
+                }
+            },
+            Decision::Stand => self.finish_hand(),
+            Decision::Double if self.rules.variant == Variant::Pontoon => {
+                let stake: u32 = self.buy_stake(&self.hands[self.active]);
+                self.money.debit(stake, Transaction::Double)?;
+                let card: Card = self.draw()?;
+                let hand: &mut Hand = &mut self.hands[self.active];
+                hand.bet += stake;
+                hand.bought += 1;
+                hand.add_card(card);
+                if self.is_complete(&self.hands[self.active]) {
+                    self.finish_hand()
+                } else {
+                    Ok(())
+                }
+            },
+            Decision::Double => {
+                let free: bool = self.free_double(&self.hands[self.active]);
+                let stake: u32 = self.hands[self.active].action();
+                if !free {
+                    self.money.debit(stake, Transaction::Double)?;
+                }
+                let card: Card = self.draw()?;
+                let hand: &mut Hand = &mut self.hands[self.active];
+                if free {
+                    hand.free += stake;
+                } else {
+                    hand.bet += stake;
+                }
+                hand.doubled = true;
+                hand.add_card(card);
+                if self.rules.allows_double_rescue() && !hand.value().bust {
+                    Ok(())
+                } else {
+                    self.finish_hand()
+                }
+            },
+            Decision::Surrender => {
+                self.hands[self.active].surrendered = true;
+                self.finish_hand()
+            },
+            Decision::Split => {
+                let free: bool = self.free_split(&self.hands[self.active]);
+                let stake: u32 = self.hands[self.active].action();
+                if !free {
+                    self.money.debit(stake, Transaction::Split)?;
+                }
+                let hand: &mut Hand = &mut self.hands[self.active];
+                hand.split = true;
+                let card: Option<Card> = hand.cards.pop();
+                let split_hand: Hand = Hand {
+                    cards: card.into_iter().collect(),
+                    split: true,
+                    split_off: true,
+                    bet: if free { 0 } else { stake },
+                    free: if free { stake } else { 0 },
+                    ..Hand::default()
+                };
+                self.hands.insert(self.active + 1, split_hand);
+                self.start_hand()
+            },
+            Decision::None => Ok(()),
+        }
+    }
+
+    fn dealer_play(&mut self) -> Result<(), BlackjackError> {
+        if self.dealer_hand.cards.len() < 2 {
+            let card: Card = self.draw()?;
+            self.dealer_hand.add_card(card);
+        }
+        self.dealer_hand.reveal();
+        let only_naturals: bool = self.hands.iter().all(|h| h.value().blackjack);
+        let all_out: bool = self.hands.iter().all(|h| h.value().bust || h.surrendered);
+        // Side bets on the dealer's hand need it played out regardless.
+        if (!only_naturals && !all_out) || self.dealer_side_bets() {
+            while self.dealer_hand.should_hit(&self.rules) && !self.is_dealer_trick() {
+                let card: Card = self.draw()?;
+                self.dealer_hand.add_card(card);
+            }
+        }
+        self.phase = Phase::Settlement;
+        Ok(())
+    }
+
+    /// The dealer stops drawing on a five card trick.
+    fn is_dealer_trick(&self) -> bool {
+        self.rules.five_card_trick() && self.dealer_hand.cards.len() >= 5
+    }
+
+    fn settle(&mut self) {
+        self.settle_side_bets(SideBetTiming::DealerTurn);
+        let dealer: HandValue = self.dealer_hand.value();
+        let dealer_22: bool = self.rules.dealer_22_pushes() && dealer.total == 22;
+        let (paid, staked): (u32, u32) = self.rules.blackjack_odds();
+        let original_bets_only: bool = self.rules.original_bets_only() && dealer.blackjack;
+        self.results.clear();
+        for hand in self.hands.iter() {
+            let player: HandValue = hand.value();
+            let spanish: bool = self.rules.variant == Variant::Spanish21;
+            let pontoon: bool = self.rules.variant == Variant::Pontoon;
+            let rank: u8 = player.pontoon_rank(hand.cards.len());
+            let status: GameStatus = if player.bust || hand.surrendered {
+                GameStatus::Lose
+            } else if pontoon {
+                // The dealer wins ties in Pontoon, pontoon against pontoon included.
+                if rank > dealer.pontoon_rank(self.dealer_hand.cards.len()) { GameStatus::Win } else { GameStatus::Lose }
+            } else if spanish && player.total == 21 {
+                // Player 21 always wins in Spanish 21.
+                GameStatus::Win
+            } else if dealer_22 && !player.blackjack {
+                GameStatus::Tie
+            } else if player.blackjack || dealer.blackjack {
+                if player.blackjack && dealer.blackjack {
+                    GameStatus::Tie
+                } else if player.blackjack {
+                    GameStatus::Win
+                } else {
+                    GameStatus::Lose
+                }
+            } else if dealer.bust || player.total > dealer.total {
+                GameStatus::Win
+            } else if player.total == dealer.total && !self.rules.dealer_wins_ties() {
+                GameStatus::Tie
+            } else {
+                GameStatus::Lose
+            };
+            let payout: u32 = match status {
+                GameStatus::Lose if hand.surrendered => hand.bet / 2,
+                // Only the opening bet is lost, doubles and split hands still standing are returned.
+                GameStatus::Lose if original_bets_only && !player.bust && hand.split_off => hand.bet,
+                GameStatus::Lose if original_bets_only && !player.bust => hand.bet - hand.bet.min(self.record.bet),
+                // A pontoon or a five card trick pays 2:1.
+                GameStatus::Win if pontoon && rank >= FIVE_CARD_TRICK => hand.bet * 3,
+                GameStatus::Win if player.blackjack => hand.bet + hand.bet * paid / staked,
+                GameStatus::Win => match spanish_bonus(hand) {
+                    Some((paid, staked)) if spanish => hand.bet + hand.bet * paid / staked,
+                    // The free bet isn't returned, only its winnings are paid.
+                    _ => hand.bet * 2 + hand.free,
+                },
+                GameStatus::Tie => hand.bet,
+                GameStatus::Lose => 0,
+            };
+            let transaction: Transaction = match status {
+                GameStatus::Tie => Transaction::PushRefund,
+                GameStatus::Lose if hand.surrendered => Transaction::SurrenderRefund,
+                GameStatus::Lose => Transaction::ExtraBetRefund,
+                _ => Transaction::Payout,
+            };
+            self.money.credit(payout, transaction);
+            self.results.push(HandResult { status, wager: hand.bet, free: hand.free, payout });
+        }
+        if dealer.blackjack {
+            self.money.credit(self.insurance * 3, Transaction::InsurancePayout);
+        }
+        self.record.player_hands = self.hands.iter().map(|h| h.cards.clone()).collect();
+        self.record.dealer_hand = self.dealer_hand.cards.clone();
+        self.record.results = self.results.clone();
+        self.record.final_wallet = self.money.wallet;
+        self.phase = Phase::Cleanup;
+    }
+
+    fn cleanup(&mut self) {
+        for hand in self.hands.iter() {
+            self.deck.discard(&hand.cards);
+        }
+        self.deck.discard(&self.dealer_hand.cards);
+        if self.deck.needs_shuffle() {
+            self.deck.shuffle();
+        }
+        self.hands.clear();
+        self.active = 0;
+        self.dealer_hand.clear_hand();
+        self.insurance = 0;
+        self.side_bets.clear();
+        self.phase = Phase::Betting;
+    }
+}
+
+/// Bonus odds of a winning Spanish 21 hand, as paid to staked, if it earns one.
+/// Doubled hands are paid even money.
+fn spanish_bonus(hand: &Hand) -> Option<(u32, u32)> {
+    if hand.doubled || hand.value().total != 21 {
+        return None;
+    }
+    let mut ranks: Vec<char> = hand.cards.iter().map(|c| c.rank).collect();
+    ranks.sort();
+    let suited: bool = hand.cards.iter().all(|c| c.suit == hand.cards[0].suit);
+    let spades: bool = suited && hand.cards[0].suit == '\u{2660}';
+    if ranks == ['6', '7', '8'] || ranks == ['7', '7', '7'] {
+        return Some(if spades { (3, 1) } else if suited { (2, 1) } else { (3, 2) });
+    }
+    match hand.cards.len() {
+        0..=4 => None,
+        5 => Some((3, 2)),
+        6 => Some((2, 1)),
+        _ => Some((3, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::Ledger;
+    use crate::rules::PeekRule;
+
+    /// Round waiting for a bet from a wallet of 1000, its shoe dealing `ranks` in order.
+    fn stacked(rules: Rules, ranks: &str) -> Round {
+        let mut rest: Vec<Card> = Deck::new(&rules, 0).fresh_cards();
+        let mut shoe: Vec<Card> = Vec::new();
+        for rank in ranks.split_whitespace() {
+            let rank: char = rank.chars().next().unwrap();
+            let index: usize = rest.iter().position(|c| c.rank == rank).unwrap();
+            shoe.push(rest.remove(index));
+        }
+        // The next card is dealt from the end of the shoe.
+        shoe.reverse();
+        let saved: SavedGame = SavedGame {
+            rules,
+            shoe,
+            discards: rest,
+            running_count: 0,
+            seed: 0,
+            shuffles: 1,
+            held_back: Vec::new(),
+            wallet: 1000,
+            last_bet: 0,
+            ledger: Ledger::default(),
+        };
+        Round::resume(saved).unwrap()
+    }
+
+    /// Stacked round with a bet of 10 dealt.
+    fn dealt(rules: Rules, ranks: &str) -> Round {
+        let mut round: Round = stacked(rules, ranks);
+        round.act(Action::Bet(10)).unwrap();
+        round.act(Action::Deal).unwrap();
+        round
+    }
+
+    /// Plays `decisions` in order, then the dealer, and settles.
+    fn play(round: &mut Round, decisions: &[Decision]) {
+        let mut decisions = decisions.iter();
+        while round.phase() != Phase::Cleanup {
+            let action: Action = match round.phase() {
+                Phase::Switch => Action::Switch(false),
+                Phase::Insurance => Action::Insurance(false),
+                Phase::PlayerTurns => Action::Decide(*decisions.next().expect("a decision is needed")),
+                Phase::DealerTurn => Action::DealerPlay,
+                Phase::Settlement => Action::Settle,
+                phase => panic!("unexpected phase {:?}", phase)
+            };
+            round.act(action).unwrap();
+        }
+        assert!(decisions.next().is_none());
+    }
+
+    fn statuses(round: &Round) -> Vec<GameStatus> {
+        round.results().iter().map(|r| r.status).collect()
+    }
+
+    #[test]
+    fn a_round_goes_through_its_phases_in_order() {
+        let mut round: Round = stacked(Rules::default(), "T 9 7 8");
+        let mut phases: Vec<Phase> = vec![round.phase()];
+        for action in [
+            Action::Bet(10),
+            Action::Deal,
+            Action::Decide(Decision::Stand),
+            Action::DealerPlay,
+            Action::Settle,
+            Action::Cleanup,
+        ] {
+            round.act(action).unwrap();
+            phases.push(round.phase());
+        }
+        assert_eq!(phases, [
+            Phase::Betting,
+            Phase::Dealing,
+            Phase::PlayerTurns,
+            Phase::DealerTurn,
+            Phase::Settlement,
+            Phase::Cleanup,
+            Phase::Betting
+        ]);
+        assert_eq!(statuses(&round), [GameStatus::Tie]);
+    }
+
+    #[test]
+    fn legal_actions_follow_the_phase() {
+        let mut round: Round = stacked(Rules::default(), "T A 9 7");
+        assert_eq!(round.legal_actions(), [Action::Bet(5)]);
+        round.act(Action::Bet(10)).unwrap();
+        assert_eq!(round.legal_actions()[0], Action::Deal);
+        round.act(Action::Deal).unwrap();
+        assert_eq!(round.legal_actions(), [Action::Insurance(false), Action::Insurance(true)]);
+        round.act(Action::Insurance(false)).unwrap();
+        assert_eq!(
+            round.legal_actions(),
+            [Action::Decide(Decision::Hit), Action::Decide(Decision::Stand), Action::Decide(Decision::Double)]
+        );
+        round.act(Action::Decide(Decision::Stand)).unwrap();
+        assert_eq!(round.legal_actions(), [Action::DealerPlay]);
+        round.act(Action::DealerPlay).unwrap();
+        assert_eq!(round.legal_actions(), [Action::Settle]);
+        round.act(Action::Settle).unwrap();
+        assert_eq!(round.legal_actions(), [Action::Cleanup]);
+    }
+
+    #[test]
+    fn illegal_actions_are_refused_without_changing_the_round() {
+        let mut round: Round = stacked(Rules::default(), "T 9 7 7");
+        let error: BlackjackError = round.act(Action::Deal).unwrap_err();
+        assert_eq!(error, BlackjackError::IllegalAction(Phase::Betting, Action::Deal));
+        assert_eq!(round.phase(), Phase::Betting);
+
+        let mut round: Round = dealt(Rules::default(), "T 9 7 7");
+        assert!(round.act(Action::Settle).is_err());
+        assert!(round.act(Action::Decide(Decision::Split)).is_err());
+        assert_eq!(round.phase(), Phase::PlayerTurns);
+        play(&mut round, &[Decision::Stand]);
+    }
+
+    #[test]
+    fn no_bet_is_legal_without_the_table_minimum() {
+        let mut round: Round = stacked(Rules::default(), "");
+        round.money.wallet = 4;
+        assert!(round.legal_actions().is_empty());
+        assert!(round.act(Action::Bet(5)).is_err());
+    }
+
+    #[test]
+    fn bets_must_be_within_the_table_limits_and_the_wallet() {
+        let rules: Rules = Rules { min_bet: 10, max_bet: 500, ..Rules::default() };
+        let mut round: Round = stacked(rules, "");
+        assert_eq!(round.act(Action::Bet(5)), Err(BlackjackError::InvalidBet(5)));
+        assert_eq!(round.act(Action::Bet(600)), Err(BlackjackError::InvalidBet(600)));
+        round.money.wallet = 300;
+        assert_eq!(
+            round.act(Action::Bet(400)),
+            Err(BlackjackError::InsufficientFunds { needed: 400, available: 300 })
+        );
+        assert_eq!(round.phase(), Phase::Betting);
+        round.act(Action::Bet(300)).unwrap();
+        assert_eq!(round.money().wallet(), 0);
+    }
+
+    #[test]
+    fn every_hand_is_played_before_the_dealer() {
+        let mut round: Round = dealt(Rules::default(), "8 9 8 7 3 T");
+        round.act(Action::Decide(Decision::Split)).unwrap();
+        assert_eq!(round.active_hand().unwrap().cards().len(), 2);
+        round.act(Action::Decide(Decision::Stand)).unwrap();
+        assert_eq!(round.phase(), Phase::PlayerTurns);
+        round.act(Action::Decide(Decision::Stand)).unwrap();
+        assert_eq!(round.phase(), Phase::DealerTurn);
+        assert!(round.active_hand().is_none());
+    }
+
+    #[test]
+    fn a_peeked_dealer_blackjack_ends_the_round() {
+        let mut round: Round = dealt(Rules::default(), "T A 9 K");
+        assert_eq!(round.phase(), Phase::Insurance);
+        round.act(Action::Insurance(false)).unwrap();
+        assert_eq!(round.phase(), Phase::Settlement);
+        assert!(round.dealer_hand().is_revealed());
+        play(&mut round, &[]);
+        assert_eq!(statuses(&round), [GameStatus::Lose]);
+        assert_eq!(round.money().wallet(), 990);
+    }
+
+    #[test]
+    fn hands_keep_their_cards_in_dealing_order() {
+        let mut round: Round = dealt(Rules::default(), "9 T 2 7 A");
+        round.act(Action::Decide(Decision::Hit)).unwrap();
+        let ranks: Vec<char> = round.hands()[0].cards().iter().map(|c| c.rank()).collect();
+        assert_eq!(ranks, ['9', '2', 'A']);
+        let sorted: Vec<char> = round.hands()[0].sorted_cards().iter().map(|c| c.rank()).collect();
+        assert_eq!(sorted, ['A', '2', '9']);
+        let dealer: Vec<char> = round.dealer_hand().visible_cards().iter().map(|c| c.rank()).collect();
+        assert_eq!(dealer, ['T']);
+    }
+
+    #[test]
+    fn blackjack_pays_three_to_two() {
+        let mut round: Round = dealt(Rules::default(), "A 9 K 7");
+        play(&mut round, &[]);
+        assert_eq!(statuses(&round), [GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1015);
+    }
+
+    #[test]
+    fn equal_totals_push() {
+        let mut round: Round = dealt(Rules::default(), "T T 8 8");
+        play(&mut round, &[Decision::Stand]);
+        assert_eq!(statuses(&round), [GameStatus::Tie]);
+        assert_eq!(round.money().wallet(), 1000);
+    }
+
+    #[test]
+    fn a_dealer_bust_pays_even_money() {
+        let mut round: Round = dealt(Rules::default(), "T T 6 6 K");
+        play(&mut round, &[Decision::Stand]);
+        assert_eq!(statuses(&round), [GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1010);
+    }
+
+    #[test]
+    fn legal_decisions_on_the_first_two_cards() {
+        let round: Round = dealt(Rules::default(), "T 9 7 7");
+        assert_eq!(round.legal_decisions(), [Decision::Hit, Decision::Stand, Decision::Double]);
+
+        let rules: Rules = Rules { surrender: true, ..Rules::default() };
+        let round: Round = dealt(rules, "8 9 8 7");
+        assert_eq!(
+            round.legal_decisions(),
+            [Decision::Hit, Decision::Stand, Decision::Double, Decision::Split, Decision::Surrender]
+        );
+    }
+
+    #[test]
+    fn legal_decisions_after_a_hit() {
+        let mut round: Round = dealt(Rules::default(), "2 9 3 7 4");
+        round.act(Action::Decide(Decision::Hit)).unwrap();
+        assert_eq!(round.legal_decisions(), [Decision::Hit, Decision::Stand]);
+    }
+
+    #[test]
+    fn legal_decisions_without_the_money_to_double() {
+        let mut round: Round = dealt(Rules::default(), "8 9 8 7");
+        round.money.wallet = 5;
+        assert_eq!(round.legal_decisions(), [Decision::Hit, Decision::Stand]);
+    }
+
+    fn spanish21() -> Rules {
+        Rules { variant: Variant::Spanish21, ..Rules::default() }
+    }
+
+    #[test]
+    fn spanish21_player_21_beats_dealer_21() {
+        let mut round: Round = dealt(spanish21(), "7 9 K 2 4 K");
+        play(&mut round, &[Decision::Hit]);
+        assert_eq!(round.dealer_hand().value().total, 21);
+        assert_eq!(statuses(&round), [GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1010);
+    }
+
+    #[test]
+    fn spanish21_five_card_21_pays_three_to_two() {
+        let mut round: Round = dealt(spanish21(), "5 9 4 K 3 4 5");
+        play(&mut round, &[Decision::Hit, Decision::Hit, Decision::Hit]);
+        assert_eq!(statuses(&round), [GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1015);
+    }
+
+    #[test]
+    fn spanish21_doubles_after_a_hit() {
+        let mut round: Round = dealt(spanish21(), "2 9 3 7 4");
+        round.act(Action::Decide(Decision::Hit)).unwrap();
+        assert_eq!(round.legal_decisions(), [Decision::Hit, Decision::Stand, Decision::Double]);
+    }
+
+    #[test]
+    fn spanish21_rescues_a_double() {
+        let mut round: Round = dealt(spanish21(), "6 9 4 K 2");
+        round.act(Action::Decide(Decision::Double)).unwrap();
+        assert_eq!(round.legal_decisions(), [Decision::Stand, Decision::Surrender]);
+        // The double comes back, the original bet is lost.
+        play(&mut round, &[Decision::Surrender]);
+        assert_eq!(round.money().wallet(), 990);
+    }
+
+    /// Switch hands A,9 and T,K into A,K and T,9 against a dealer 6,T drawing `draw`.
+    fn switched_into_21(draw: &str) -> Round {
+        let rules: Rules = Rules { variant: Variant::Switch, ..Rules::default() };
+        let mut round: Round = dealt(rules, &format!("A T 6 9 K T {}", draw));
+        round.act(Action::Switch(true)).unwrap();
+        assert_eq!(round.hands()[0].value().total, 21);
+        assert!(!round.hands()[0].is_blackjack());
+        play(&mut round, &[Decision::Stand]);
+        round
+    }
+
+    #[test]
+    fn switched_21_pushes_a_dealer_22() {
+        let round: Round = switched_into_21("6");
+        assert_eq!(round.dealer_hand().value().total, 22);
+        assert_eq!(statuses(&round), [GameStatus::Tie, GameStatus::Tie]);
+        assert_eq!(round.money().wallet(), 1000);
+    }
+
+    #[test]
+    fn switched_21_pushes_a_dealer_three_card_21() {
+        let round: Round = switched_into_21("5");
+        assert_eq!(round.dealer_hand().value().total, 21);
+        assert_eq!(statuses(&round), [GameStatus::Tie, GameStatus::Lose]);
+        assert_eq!(round.money().wallet(), 990);
+    }
+
+    #[test]
+    fn dealt_natural_beats_a_dealer_22_in_switch() {
+        let rules: Rules = Rules { variant: Variant::Switch, ..Rules::default() };
+        let mut round: Round = dealt(rules, "A T 6 K 9 T 6");
+        assert!(round.hands()[0].is_blackjack());
+        play(&mut round, &[Decision::Stand]);
+        assert_eq!(statuses(&round), [GameStatus::Win, GameStatus::Tie]);
+        assert_eq!(round.money().wallet(), 1010);
+    }
+
+    #[test]
+    fn a_dealer_22_pushes_both_switch_hands() {
+        let rules: Rules = Rules { variant: Variant::Switch, ..Rules::default() };
+        let mut round: Round = dealt(rules, "T T 6 8 9 T 6");
+        play(&mut round, &[Decision::Stand, Decision::Stand]);
+        assert_eq!(round.dealer_hand().value().total, 22);
+        assert_eq!(statuses(&round), [GameStatus::Tie, GameStatus::Tie]);
+        assert_eq!(round.money().wallet(), 1000);
+    }
+
+    #[test]
+    fn switch_bets_go_on_both_hands() {
+        let rules: Rules = Rules { variant: Variant::Switch, ..Rules::default() };
+        let mut round: Round = stacked(rules, "");
+        round.money.wallet = 300;
+        assert_eq!(
+            round.act(Action::Bet(200)),
+            Err(BlackjackError::InsufficientFunds { needed: 400, available: 300 })
+        );
+        round.act(Action::Bet(150)).unwrap();
+        assert_eq!(round.money().wallet(), 0);
+    }
+
+    #[test]
+    fn double_exposure_shows_both_dealer_cards_and_offers_no_insurance() {
+        let rules: Rules = Rules { variant: Variant::DoubleExposure, ..Rules::default() };
+        let round: Round = dealt(rules, "T A 9 5");
+        assert_eq!(round.dealer_hand().visible_cards().len(), 2);
+        assert_eq!(round.phase(), Phase::PlayerTurns);
+    }
+
+    #[test]
+    fn double_exposure_dealer_wins_ties_and_blackjack_pays_even_money() {
+        let rules: Rules = Rules { variant: Variant::DoubleExposure, ..Rules::default() };
+        let mut round: Round = dealt(rules, "T T 8 8");
+        play(&mut round, &[Decision::Stand]);
+        assert_eq!(statuses(&round), [GameStatus::Lose]);
+        assert_eq!(round.money().wallet(), 990);
+
+        let mut round: Round = dealt(rules, "A 9 K 7");
+        play(&mut round, &[]);
+        assert_eq!(statuses(&round), [GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1010);
+    }
+
+    #[test]
+    fn free_bet_double_pays_only_the_winnings_of_the_free_stake() {
+        let rules: Rules = Rules { variant: Variant::FreeBet, ..Rules::default() };
+        let mut round: Round = dealt(rules, "6 6 4 T T 7");
+        play(&mut round, &[Decision::Double]);
+        assert_eq!((round.hands()[0].bet(), round.hands()[0].free()), (10, 10));
+        assert_eq!(statuses(&round), [GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1020);
+    }
+
+    #[test]
+    fn free_bet_split_hand_is_staked_by_the_house() {
+        let rules: Rules = Rules { variant: Variant::FreeBet, ..Rules::default() };
+        let mut round: Round = dealt(rules, "8 9 8 7 T T 9");
+        play(&mut round, &[Decision::Split, Decision::Stand, Decision::Stand]);
+        assert_eq!((round.hands()[1].bet(), round.hands()[1].free()), (0, 10));
+        assert!(round.dealer_hand().value().bust);
+        assert_eq!(statuses(&round), [GameStatus::Win, GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1020);
+    }
+
+    #[test]
+    fn free_bet_dealer_22_pushes() {
+        let rules: Rules = Rules { variant: Variant::FreeBet, ..Rules::default() };
+        let mut round: Round = dealt(rules, "T T 8 6 6");
+        play(&mut round, &[Decision::Stand]);
+        assert_eq!(round.dealer_hand().value().total, 22);
+        assert_eq!(statuses(&round), [GameStatus::Tie]);
+        assert_eq!(round.money().wallet(), 1000);
+    }
+
+    #[test]
+    fn pontoon_dealer_wins_ties_and_five_card_trick_pays_two_to_one() {
+        let rules: Rules = Rules { variant: Variant::Pontoon, ..Rules::default() };
+        let mut round: Round = dealt(rules, "T T 8 8");
+        assert!(round.dealer_hand().visible_cards().is_empty());
+        play(&mut round, &[Decision::Stand]);
+        assert_eq!(statuses(&round), [GameStatus::Lose]);
+        assert_eq!(round.money().wallet(), 990);
+
+        let mut round: Round = dealt(rules, "2 T 3 8 2 3 4");
+        play(&mut round, &[Decision::Hit, Decision::Hit, Decision::Hit]);
+        assert_eq!(statuses(&round), [GameStatus::Win]);
+        assert_eq!(round.money().wallet(), 1020);
+    }
+
+    #[test]
+    fn pontoon_sticks_from_15_and_buys_instead_of_doubling() {
+        let rules: Rules = Rules { variant: Variant::Pontoon, ..Rules::default() };
+        let mut round: Round = dealt(rules, "T 9 4 7 2");
+        assert_eq!(round.legal_decisions(), [Decision::Hit, Decision::Double]);
+        // A bought card adds the stake and leaves the hand open.
+        round.act(Action::Decide(Decision::Double)).unwrap();
+        assert_eq!(round.hands()[0].bet(), 20);
+        assert_eq!(round.phase(), Phase::PlayerTurns);
+
+        let rules: Rules = Rules { surrender: true, ..rules };
+        let round: Round = dealt(rules, "T 9 6 7");
+        assert_eq!(round.legal_decisions(), [Decision::Hit, Decision::Stand, Decision::Double]);
+    }
+
+    /// 8,8 against a ten, the first 8 doubled on 4 into a ten and busted, the second
+    /// standing on 17, then the dealer draws an ace for blackjack.
+    fn split_into_dealer_blackjack(peek: PeekRule) -> Round {
+        let rules: Rules = Rules { peek, ..Rules::default() };
+        let mut round: Round = dealt(rules, "8 T 8 4 T 9 A");
+        assert_eq!(round.dealer_hand().cards.len(), 1);
+        play(&mut round, &[Decision::Split, Decision::Double, Decision::Stand]);
+        assert!(round.dealer_hand().is_blackjack());
+        round
+    }
+
+    #[test]
+    fn no_hole_card_dealer_blackjack_takes_every_bet() {
+        let round: Round = split_into_dealer_blackjack(PeekRule::NoHoleCard);
+        assert_eq!(round.results().iter().map(|r| r.payout).sum::<u32>(), 0);
+        assert_eq!(round.money().wallet(), 970);
+    }
+
+    #[test]
+    fn original_bets_only_returns_standing_split_hands_but_not_busted_doubles() {
+        let round: Round = split_into_dealer_blackjack(PeekRule::OriginalBetsOnly);
+        assert_eq!(statuses(&round), [GameStatus::Lose, GameStatus::Lose]);
+        assert_eq!(round.results()[0].payout, 0);
+        assert_eq!(round.results()[1].payout, 10);
+        assert_eq!(round.money().wallet(), 980);
+    }
+
+    #[test]
+    fn a_perfect_pairs_bet_is_paid_on_the_deal() {
+        let mut round: Round = stacked(Rules::default(), "8 9 8 7");
+        round.act(Action::Bet(10)).unwrap();
+        round.act(Action::SideBet(SideBetKind::PerfectPairs, 5)).unwrap();
+        assert!(!round.is_legal(Action::SideBet(SideBetKind::PerfectPairs, 5)));
+        round.act(Action::Deal).unwrap();
+        // 8 of spades and 8 of hearts, a mixed pair paying 6:1.
+        assert_eq!(round.side_bets(), [SideBetResult { kind: SideBetKind::PerfectPairs, wager: 5, payout: 35 }]);
+        assert_eq!(round.money().wallet(), 1020);
+    }
+
+    #[test]
+    fn a_buster_blackjack_bet_is_paid_once_the_dealer_busts() {
+        let mut round: Round = stacked(Rules::default(), "T T 6 6 K");
+        round.act(Action::Bet(10)).unwrap();
+        round.act(Action::SideBet(SideBetKind::BusterBlackjack, 5)).unwrap();
+        round.act(Action::Deal).unwrap();
+        assert_eq!(round.side_bets()[0].payout, 0);
+        play(&mut round, &[Decision::Stand]);
+        assert_eq!(round.side_bets(), [SideBetResult { kind: SideBetKind::BusterBlackjack, wager: 5, payout: 10 }]);
+        assert_eq!(round.money().wallet(), 1015);
+    }
+}
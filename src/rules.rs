@@ -0,0 +1,250 @@
+use crate::error::BlackjackError;
+use crate::side_bet::SideBets;
+use serde::{Deserialize, Serialize};
+
+/// Whether the dealer takes a hole card and checks it for blackjack.
+/// Written `hole-card`, `enhc` or `obo`, the names of earlier files still read.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PeekRule {
+    /// US style: the hole card is dealt face down and checked under an ace or ten.
+    #[default]
+    #[serde(alias = "HoleCard")]
+    HoleCard,
+    /// European no hole card (ENHC): the second card is dealt after the players act
+    /// and a dealer blackjack takes every bet, doubles and splits included.
+    #[serde(rename = "enhc", alias = "NoHoleCard")]
+    NoHoleCard,
+    /// No hole card, but a dealer blackjack only takes the original bets (OBO),
+    /// doubles and splits are returned.
+    #[serde(rename = "obo", alias = "OriginalBetsOnly")]
+    OriginalBetsOnly,
+}
+
+/// Which game is dealt at the table.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Variant {
+    /// Blackjack as dealt in most casinos.
+    #[default]
+    Classic,
+    /// 48-card decks without the tens, player 21 always wins, bonuses for
+    /// five or more card 21s, 6-7-8 and 7-7-7, late surrender and double-down rescue.
+    #[serde(rename = "spanish21")]
+    Spanish21,
+    /// Two hands per seat whose second cards may be swapped, blackjack pays
+    /// even money and a dealer 22 pushes every hand still standing.
+    Switch,
+    /// Both dealer cards dealt face up, the dealer wins ties except against a
+    /// player blackjack and blackjack pays even money.
+    DoubleExposure,
+    /// The house puts up the extra stake on hard 9, 10 and 11 doubles and on
+    /// splitting any pair but tens, and a dealer 22 pushes every hand still standing.
+    FreeBet,
+    /// British rules: twist, stick or buy, both dealer cards face down, a five
+    /// card trick beats everything but a pontoon and the dealer wins ties.
+    Pontoon,
+}
+
+/// What a winning blackjack pays.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlackjackPayout {
+    #[default]
+    #[serde(rename = "3:2")]
+    ThreeToTwo,
+    #[serde(rename = "6:5")]
+    SixToFive,
+    #[serde(rename = "1:1")]
+    EvenMoney,
+}
+
+/// Table configuration.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    pub min_bet: u32,
+    pub max_bet: u32,
+    pub decks: u8,
+    /// Fraction of the shoe dealt before the cut card comes out.
+    pub penetration: f32,
+    pub dealer_hits_soft_17: bool,
+    pub peek: PeekRule,
+    /// Late surrender of the first two cards, always allowed in Spanish 21.
+    pub surrender: bool,
+    /// Blackjack payout on classic and Spanish 21 tables, Switch and Double Exposure always pay even money.
+    pub blackjack_pays: BlackjackPayout,
+    pub variant: Variant,
+    /// Paytables of the side bets, which are all offered.
+    pub side_bets: SideBets,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            min_bet: 5,
+            max_bet: 1000,
+            decks: 1,
+            penetration: 0.75,
+            dealer_hits_soft_17: false,
+            peek: PeekRule::HoleCard,
+            surrender: false,
+            blackjack_pays: BlackjackPayout::ThreeToTwo,
+            variant: Variant::Classic,
+            side_bets: SideBets::default(),
+        }
+    }
+}
+
+impl Rules {
+    /// Cards in each deck of the shoe.
+    pub fn deck_size(&self) -> usize {
+        match self.variant {
+            Variant::Spanish21 => 48,
+            _ => 52,
+        }
+    }
+
+    /// Hands the player starts every round with, each carrying the bet.
+    pub fn base_hands(&self) -> usize {
+        match self.variant {
+            Variant::Switch => 2,
+            _ => 1,
+        }
+    }
+
+    /// Blackjack odds as paid to staked.
+    pub fn blackjack_odds(&self) -> (u32, u32) {
+        match (self.variant, self.blackjack_pays) {
+            (Variant::Switch | Variant::DoubleExposure, _) | (_, BlackjackPayout::EvenMoney) => (1, 1),
+            (_, BlackjackPayout::SixToFive) => (6, 5),
+            (_, BlackjackPayout::ThreeToTwo) => (3, 2),
+        }
+    }
+
+    /// A dealer busting with exactly 22 pushes the hands still standing instead of losing.
+    pub fn dealer_22_pushes(&self) -> bool {
+        matches!(self.variant, Variant::Switch | Variant::FreeBet)
+    }
+
+    /// Some doubles and splits are free bets: staked by the house, only their winnings are paid.
+    pub fn free_bets(&self) -> bool {
+        self.variant == Variant::FreeBet
+    }
+
+    /// The dealer takes a second card up front and checks it for blackjack.
+    pub fn has_hole_card(&self) -> bool {
+        self.peek == PeekRule::HoleCard
+    }
+
+    /// A dealer blackjack dealt after the players act only takes the original bets.
+    pub fn original_bets_only(&self) -> bool {
+        self.peek == PeekRule::OriginalBetsOnly
+    }
+
+    /// The hole card is dealt face up.
+    pub fn dealer_exposed(&self) -> bool {
+        self.variant == Variant::DoubleExposure
+    }
+
+    /// Equal totals lose, a blackjack against a dealer blackjack still pushes.
+    pub fn dealer_wins_ties(&self) -> bool {
+        self.variant == Variant::DoubleExposure
+    }
+
+    /// Neither dealer card is shown until the players are done.
+    pub fn dealer_face_down(&self) -> bool {
+        self.variant == Variant::Pontoon
+    }
+
+    /// Five cards without busting end the hand and beat any total.
+    pub fn five_card_trick(&self) -> bool {
+        self.variant == Variant::Pontoon
+    }
+
+    /// Late surrender is offered, never in Pontoon.
+    pub fn allows_surrender(&self) -> bool {
+        (self.surrender && self.variant != Variant::Pontoon) || self.variant == Variant::Spanish21
+    }
+
+    /// A doubled hand can be surrendered, giving up the original bet and keeping the double.
+    pub fn allows_double_rescue(&self) -> bool {
+        self.variant == Variant::Spanish21
+    }
+
+    /// Doubling is allowed on any number of cards, not only the first two.
+    pub fn double_any_cards(&self) -> bool {
+        self.variant == Variant::Spanish21
+    }
+
+    /// Refuses tables that can't be dealt: bad limits, no decks, a penetration outside (0, 1]
+    /// or a variant that needs a hole card played without one.
+    pub fn validate(&self) -> Result<(), BlackjackError> {
+        if self.min_bet == 0 || self.min_bet > self.max_bet {
+            return Err(BlackjackError::InvalidConfig(
+                format!("bet limits {}-{} are not valid", self.min_bet, self.max_bet)
+            ));
+        }
+        if self.decks == 0 {
+            return Err(BlackjackError::InvalidConfig("the shoe needs at least one deck".to_string()));
+        }
+        if !(self.penetration > 0.0 && self.penetration <= 1.0) {
+            return Err(BlackjackError::InvalidConfig(
+                format!("penetration must be in (0, 1], got {}", self.penetration)
+            ));
+        }
+        if self.dealer_exposed() && !self.has_hole_card() {
+            return Err(BlackjackError::InvalidConfig(
+                "double exposure deals the hole card face up, it can't be played without one".to_string()
+            ));
+        }
+        if self.variant == Variant::Pontoon && !self.has_hole_card() {
+            return Err(BlackjackError::InvalidConfig(
+                "pontoon deals the dealer both cards up front, it can't be played without a hole card".to_string()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peek_rules_are_spelled_in_kebab_case() {
+        for (rule, name) in [(PeekRule::HoleCard, "hole-card"), (PeekRule::NoHoleCard, "enhc"), (PeekRule::OriginalBetsOnly, "obo")] {
+            let json: String = format!("\"{}\"", name);
+            assert_eq!(serde_json::to_string(&rule).unwrap(), json);
+            assert_eq!(serde_json::from_str::<PeekRule>(&json).unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn peek_rules_of_earlier_files_still_read() {
+        for (rule, name) in [(PeekRule::HoleCard, "HoleCard"), (PeekRule::NoHoleCard, "NoHoleCard"), (PeekRule::OriginalBetsOnly, "OriginalBetsOnly")] {
+            assert_eq!(serde_json::from_str::<PeekRule>(&format!("\"{}\"", name)).unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn table_config_reads_the_peek_rule() {
+        let rules: Rules = toml::from_str("decks = 6\npeek = \"enhc\"").unwrap();
+        assert_eq!(rules.peek, PeekRule::NoHoleCard);
+        assert!(toml::from_str::<Rules>("peek = \"no-hole-card\"").is_err());
+    }
+
+    #[test]
+    fn tables_that_cant_be_dealt_are_invalid() {
+        assert!(Rules::default().validate().is_ok());
+        for rules in [
+            Rules { min_bet: 0, ..Rules::default() },
+            Rules { min_bet: 50, max_bet: 10, ..Rules::default() },
+            Rules { decks: 0, ..Rules::default() },
+            Rules { penetration: 1.5, ..Rules::default() },
+            Rules { variant: Variant::Pontoon, peek: PeekRule::NoHoleCard, ..Rules::default() },
+        ] {
+            assert!(matches!(rules.validate(), Err(BlackjackError::InvalidConfig(_))));
+        }
+    }
+}
@@ -0,0 +1,435 @@
+use crate::view;
+use blackrust::profile::{Profile, SavedGame};
+use blackrust::{
+    chips_value, Action, BlackjackError, Chart, CountingSystem, Decision, Deck, Game, GameStatus, Hand, Phase, Round,
+    Rules, SideBetTiming, Strategy, Variant
+};
+use std::io;
+
+/// Next line typed by the player, `None` once the input is closed.
+pub fn read_line() -> Option<String> {
+    let mut input: String = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input.trim().to_string())
+    }
+}
+
+/// A player at the terminal, playing a game with their profile.
+pub struct Session {
+    game: Game,
+    profile: Option<Profile>,
+    /// The rules were chosen for this session, a player's saved rules don't replace them.
+    own_rules: bool,
+    /// Tells the player what the chart says after each decision.
+    coach: bool,
+    /// Counting system the count is shown in before every bet.
+    count: Option<CountingSystem>,
+    /// The game was saved during this session, its wallet stays with the save.
+    saved: bool,
+    color: bool,
+}
+
+impl Session {
+    pub fn new(game: Game, color: bool) -> Self {
+        Session { game, profile: None, own_rules: false, coach: false, count: None, saved: false, color }
+    }
+
+    /// Keeps the table rules when a player with other saved rules sits down.
+    pub fn keep_rules(&mut self) {
+        self.own_rules = true;
+    }
+
+    /// Shows the running and true count in `system` before every bet.
+    pub fn show_count(&mut self, system: CountingSystem) {
+        self.count = Some(system);
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Asks for the starting wallet, false when the input ends first.
+    fn make_wallet(&mut self) -> bool {
+        loop {
+            println!("How much money do you have? ");
+            let Some(input) = read_line() else {
+                return false;
+            };
+            match input.parse::<u32>() {
+                Ok(money) => {
+                    self.game.deposit(money);
+                    return true;
+                },
+                Err(_) => println!("Please enter a whole amount of money.")
+            }
+        }
+    }
+
+    /// Asks for a bet, either as an amount or as a list of chips.
+    /// An empty answer repeats the last one, `save`, `quit` or the end of the input end the session.
+    fn make_bet(&mut self) -> Option<u32> {
+        loop {
+            let rules: &Rules = self.game.round().rules();
+            println!("How much do you bet? ({}-{}, save or quit) ", rules.min_bet, rules.max_bet);
+            let input: String = read_line()?;
+            if input == "quit" {
+                return None;
+            }
+            if input == "save" {
+                match self.save_game() {
+                    Ok(()) => {
+                        println!("Game saved.");
+                        return None;
+                    },
+                    Err(error) => {
+                        println!("{}", error);
+                        continue;
+                    }
+                }
+            }
+            let last_bet: u32 = self.game.money().last_bet();
+            if input.is_empty() && last_bet != 0 {
+                return Some(last_bet);
+            }
+            let amounts: Result<Vec<u32>, _> = input
+                .split_whitespace()
+                .map(|a| a.parse::<u32>())
+                .collect();
+            match amounts {
+                Ok(amounts) if amounts.len() == 1 => return Some(amounts[0]),
+                Ok(amounts) if amounts.len() > 1 => match chips_value(&amounts) {
+                    Ok(bet) => return Some(bet),
+                    Err(error) => println!("{}", error)
+                },
+                _ => println!("Please enter a whole amount or a list of chips to bet.")
+            }
+        }
+    }
+
+    /// Asks for each of the player's side bets, an empty answer keeping the usual amount.
+    /// `None` when the input ends.
+    fn make_side_bets(&self) -> Option<Vec<Action>> {
+        let max_bet: u32 = self.game.round().rules().max_bet;
+        let mut actions: Vec<Action> = Vec::new();
+        for (kind, amount) in self.game.side_bets().iter() {
+            let amount: u32 = loop {
+                println!("{} side bet? (empty for {}, 0 for none) ", kind.name(), amount);
+                let input: String = read_line()?;
+                if input.is_empty() {
+                    break *amount;
+                }
+                match input.parse::<u32>() {
+                    Ok(amount) if amount <= max_bet => break amount,
+                    _ => println!("Please enter a whole amount up to {}.", max_bet)
+                }
+            };
+            if amount > 0 {
+                actions.push(Action::SideBet(*kind, amount));
+            }
+        }
+        Some(actions)
+    }
+
+    fn save_game(&mut self) -> Result<(), BlackjackError> {
+        match &self.profile {
+            Some(profile) => self.game.save()?.save(&profile.name)?,
+            None => return Err(BlackjackError::Storage("guests can't save games".to_string()))
+        }
+        self.saved = true;
+        Ok(())
+    }
+
+    /// Loads the player's profile and either resumes their saved game or sets up the wallet.
+    /// Returns false when the input ends first.
+    fn sit_down(&mut self) -> bool {
+        loop {
+            println!("Player name (empty to play as a guest): ");
+            let Some(name) = read_line() else {
+                return false;
+            };
+            if name.is_empty() {
+                break;
+            }
+            match Profile::load_or_create(&name) {
+                Ok(profile) => {
+                    view::profile(&profile);
+                    self.profile = Some(profile);
+                    break;
+                },
+                Err(error) => println!("{}", error)
+            }
+        }
+        let min_bet: u32 = self.game.round().rules().min_bet;
+        let Some(profile) = &self.profile else {
+            return self.game.money().wallet() >= min_bet || self.make_wallet();
+        };
+        // Money given on the command line or in the config comes on top of what the player has stored.
+        let (name, bankroll, rules): (String, u32, Rules) = (profile.name.clone(), profile.bankroll, profile.rules);
+        if SavedGame::exists(&name) {
+            println!("Resume saved game? [y/n] ");
+            let Some(answer) = read_line() else {
+                return false;
+            };
+            if answer == "y" {
+                match SavedGame::load(&name).and_then(|saved| self.game.resume(saved)) {
+                    Ok(()) => {
+                        self.game.deposit(bankroll);
+                        if let Err(error) = SavedGame::remove(&name) {
+                            println!("{}", error);
+                        }
+                        return true;
+                    },
+                    Err(error) => println!("{}", error)
+                }
+            }
+        }
+        if !self.own_rules && rules != *self.game.round().rules() {
+            if let Err(error) = self.game.change_rules(rules) {
+                println!("{}", error);
+            }
+        }
+        self.game.deposit(bankroll);
+        self.game.money().wallet() >= self.game.round().rules().min_bet || self.make_wallet()
+    }
+
+    /// Stores the session into the player's profile.
+    fn stand_up(&mut self, start: u32) {
+        let wallet: u32 = self.game.money().wallet();
+        if let Some(profile) = self.profile.as_mut() {
+            profile.stats.net += wallet as i64 - start as i64;
+            // The wallet stays on the table with the saved game.
+            profile.bankroll = if self.saved { 0 } else { wallet };
+            profile.rules = *self.game.round().rules();
+            if let Err(error) = profile.save() {
+                println!("{}", error);
+            }
+            view::profile(profile);
+        }
+    }
+
+    /// Asks until the answer names a decision, `None` when the input ends.
+    fn player_decision(&self) -> Option<Decision> {
+        let round: &Round = self.game.round();
+        let hand: Option<&Hand> = round.active_hand();
+        let free_double: bool = hand.is_some_and(|h| round.free_double(h));
+        let free_split: bool = hand.is_some_and(|h| round.free_split(h));
+        let pontoon: bool = round.rules().variant == Variant::Pontoon;
+        let legal: Vec<&str> = round
+            .legal_decisions()
+            .iter()
+            .map(|d| match d {
+                Decision::Hit if pontoon => "Twist",
+                Decision::Hit => "Hit",
+                Decision::Stand if pontoon => "Stick",
+                Decision::Stand => "Stand",
+                Decision::Double if pontoon => "Buy",
+                Decision::Double if free_double => "free Double",
+                Decision::Double => "Double",
+                Decision::Split if free_split => "free sPlit",
+                Decision::Split => "sPlit",
+                Decision::Surrender => "suRrender",
+                Decision::None => "",
+            })
+            .collect();
+        loop {
+            match legal.split_last() {
+                Some((last, [])) => println!("{}? ", last),
+                Some((last, rest)) => println!("{} or {}? ", rest.join(", "), last),
+                None => println!()
+            }
+            match (read_line()?.parse::<char>(), pontoon) {
+                (Ok('h'), false) | (Ok('t'), true) => return Some(Decision::Hit),
+                (Ok('d'), false) | (Ok('b'), true) => return Some(Decision::Double),
+                (Ok('p'), _) => return Some(Decision::Split),
+                (Ok('s'), _) => return Some(Decision::Stand),
+                (Ok('r'), false) => return Some(Decision::Surrender),
+                _ => {}
+            }
+        }
+    }
+
+    fn view_hands(&self) {
+        let hands: &[Hand] = self.game.round().hands();
+        if hands.len() == 1 {
+            view::hand(&hands[0], self.color);
+        } else {
+            for (i, hand) in hands.iter().enumerate() {
+                print!("Hand {}: ", i + 1);
+                view::hand(hand, self.color);
+            }
+        }
+    }
+
+    fn view_side_bets(&self, timing: SideBetTiming) {
+        let round: &Round = self.game.round();
+        for bet in round.side_bets().iter().filter(|b| round.side_bet_timing(b.kind) == timing) {
+            if bet.payout > 0 {
+                println!("{} pays {}.", bet.kind.name(), bet.payout);
+            } else {
+                println!("{} loses.", bet.kind.name());
+            }
+        }
+    }
+
+    fn view_results(&self) {
+        for result in self.game.round().results() {
+            match result.status {
+                GameStatus::Win => println!("You win."),
+                GameStatus::Tie => println!("It's a tie."),
+                GameStatus::Lose => println!("You lose.")
+            }
+        }
+    }
+
+    pub fn play(&mut self) {
+        if !self.sit_down() {
+            return;
+        }
+        let start: u32 = self.game.money().wallet();
+        let mut side_bets: Vec<Action> = Vec::new();
+        // Once the input ends, the chart finishes the round on the table.
+        let mut closed: bool = false;
+        loop {
+            let action: Action = match self.game.round().phase() {
+                Phase::Betting => {
+                    if self.game.round().legal_actions().is_empty() {
+                        break;
+                    }
+                    if let Some(system) = self.count {
+                        let deck: &Deck = self.game.round().deck();
+                        println!("Running count: {:+}, True count: {:+.1}", deck.count(system), deck.true_count(system));
+                    }
+                    if closed {
+                        break;
+                    }
+                    let Some(bet) = self.make_bet() else {
+                        break;
+                    };
+                    let Some(bets) = self.make_side_bets() else {
+                        break;
+                    };
+                    side_bets = bets;
+                    Action::Bet(bet)
+                },
+                Phase::Switch => {
+                    let answer: Option<String> = match closed {
+                        true => None,
+                        false => {
+                            println!("Switch the second cards? [y/n] ");
+                            read_line()
+                        }
+                    };
+                    closed = answer.is_none();
+                    Action::Switch(answer.is_some_and(|answer| answer == "y"))
+                },
+                Phase::Insurance => {
+                    let answer: Option<String> = match closed {
+                        true => None,
+                        false => {
+                            println!("Insurance? [y/n] ");
+                            read_line()
+                        }
+                    };
+                    closed = answer.is_none();
+                    Action::Insurance(answer.is_some_and(|answer| answer == "y"))
+                },
+                Phase::PlayerTurns => match if closed { None } else { self.player_decision() } {
+                    Some(decision) => {
+                        self.grade_decision(decision);
+                        Action::Decide(decision)
+                    },
+                    None => {
+                        closed = true;
+                        Action::Decide(self.game.chart_decision())
+                    }
+                },
+                Phase::Dealing => side_bets.pop().unwrap_or(Action::Deal),
+                Phase::DealerTurn => Action::DealerPlay,
+                Phase::Settlement => Action::Settle,
+                Phase::Cleanup => Action::Cleanup,
+            };
+            if let Err(error) = self.game.act(action) {
+                println!("{}", error);
+                if error == BlackjackError::EmptyShoe {
+                    break;
+                }
+                continue;
+            }
+            match action {
+                Action::Deal => {
+                    let bet: u32 = self.game.round().hands()[0].bet();
+                    view::money(self.game.money().wallet(), bet);
+                    println!("Dealer's hand: ");
+                    view::dealer_hand(self.game.round().dealer_hand(), self.color);
+                    println!("Player's hand: ");
+                    self.view_hands();
+                    self.view_side_bets(SideBetTiming::Deal);
+                },
+                Action::Decide(Decision::Stand) | Action::Switch(false) => {},
+                Action::Decide(_) | Action::Switch(true) => {
+                    println!("Player's hand: ");
+                    self.view_hands();
+                },
+                Action::DealerPlay => {
+                    println!("Dealer's hand: ");
+                    view::dealer_hand(self.game.round().dealer_hand(), self.color);
+                },
+                Action::Settle => {
+                    if self.game.round().dealer_hand().is_blackjack() {
+                        match self.game.round().rules().variant {
+                            Variant::Pontoon => println!("Dealer has pontoon!"),
+                            _ => println!("Dealer has blackjack!"),
+                        }
+                        view::dealer_hand(self.game.round().dealer_hand(), self.color);
+                    }
+                    self.view_results();
+                    self.view_side_bets(SideBetTiming::DealerTurn);
+                    if let Err(error) = self.game.write_history() {
+                        println!("{}", error);
+                    }
+                    if let Some(profile) = self.profile.as_mut() {
+                        profile.stats.rounds += 1;
+                        for result in self.game.round().results() {
+                            profile.stats.record_result(result.status);
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+        view::ledger_summary(self.game.money().ledger());
+        self.stand_up(start);
+    }
+
+    /// Plays like `play`, telling the player after each decision what the chart would have done.
+    pub fn train(&mut self) {
+        self.coach = true;
+        self.play();
+    }
+
+    /// Tracks how often the player follows the chart.
+    fn grade_decision(&mut self, decision: Decision) {
+        let round: &Round = self.game.round();
+        let legal: Vec<Decision> = round.legal_decisions();
+        if !legal.contains(&decision) {
+            return;
+        }
+        let Some(hand) = round.active_hand() else {
+            return;
+        };
+        let chart: &Chart = self.game.chart();
+        let expected: Decision = chart.decide(hand, round.dealer_hand().visible_cards(), &legal);
+        let correct: bool = expected == decision;
+        if self.coach {
+            if correct {
+                println!("Correct, {} says {:?}.", chart.name(), expected);
+            } else {
+                println!("{} says {:?} here, not {:?}.", chart.name(), expected, decision);
+            }
+        }
+        if let Some(profile) = self.profile.as_mut() {
+            profile.stats.record_decision(correct);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::card::Card;
+use crate::error::BlackjackError;
+use crate::rules::Rules;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// The shoe the cards are dealt from, with its discard tray.
+#[derive(Clone)]
+pub struct Deck {
+    pub(crate) cards: Vec<Card>,
+    pub(crate) discards: Vec<Card>,
+    pub(crate) size: usize,
+    /// Cards in each deck, 48 when the tens are taken out for Spanish 21.
+    pub(crate) deck_size: usize,
+    pub(crate) penetration: f32,
+    pub(crate) seed: u64,
+    pub(crate) shuffles: u64,
+    /// Cards that were on the table when the shoe was last reshuffled mid round, so left out of it.
+    pub(crate) held_back: Vec<Card>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new(&Rules::default(), rand::thread_rng().gen())
+    }
+}
+
+impl Deck {
+    /// Shuffled shoe made of the decks the rules ask for.
+    /// Every shuffle is drawn from `seed`, so the same seed deals the same cards.
+    pub fn new(rules: &Rules, seed: u64) -> Self {
+        let mut deck: Deck = Deck {
+            cards: Vec::new(),
+            discards: Vec::new(),
+            size: rules.decks as usize * rules.deck_size(),
+            deck_size: rules.deck_size(),
+            penetration: rules.penetration,
+            seed,
+            shuffles: 0,
+            held_back: Vec::new(),
+        };
+        deck.shuffle();
+        deck
+    }
+
+    /// The shoe as it was `position` cards after its `shuffle`th shuffle, counting from 1.
+    /// `held_back` are the cards a mid round reshuffle left out of the shoe, empty after a fresh shuffle.
+    pub fn at(rules: &Rules, seed: u64, shuffle: u64, position: usize, held_back: &[Card]) -> Self {
+        let mut deck: Deck = Deck::new(rules, seed);
+        deck.shuffles = shuffle.saturating_sub(1);
+        if held_back.is_empty() {
+            deck.shuffle();
+        } else {
+            deck.cards.clear();
+            deck.discards = deck.fresh_cards();
+            for card in held_back {
+                if let Some(index) = deck.discards.iter().position(|c| c == card) {
+                    deck.discards.swap_remove(index);
+                }
+            }
+            deck.reshuffle();
+            deck.discards.extend_from_slice(held_back);
+        }
+        let dealt: usize = position.saturating_sub(held_back.len());
+        let remaining: usize = deck.cards.len().saturating_sub(dealt);
+        deck.discards.extend(deck.cards.split_off(remaining));
+        deck
+    }
+
+    /// Every card of the shoe, unshuffled.
+    pub fn fresh_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = Vec::new();
+        let ranks: [char;13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'A', 'J', 'Q', 'K'];
+        let suits: [char;4] = ['\u{2660}', '\u{2665}', '\u{2663}', '\u{2666}'];
+        for _ in 0..self.size / self.deck_size {
+            for suit in suits {
+                for rank in ranks {
+                    if rank == 'T' && self.deck_size == 48 {
+                        continue;
+                    }
+                    cards.push(Card::of(rank, suit));
+                }
+            }
+        }
+        cards
+    }
+
+    fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed.wrapping_add(self.shuffles))
+    }
+
+    /// Puts every card back in the shoe and shuffles it.
+    pub(crate) fn shuffle(&mut self) {
+        self.cards = self.fresh_cards();
+        let mut rng: StdRng = self.rng();
+        self.cards.shuffle(&mut rng);
+        self.discards.clear();
+        self.held_back.clear();
+        self.shuffles += 1;
+    }
+
+    /// Deals the next card, shuffling the discard tray back in if the shoe runs out mid round.
+    pub(crate) fn hit(&mut self) -> Result<Card, BlackjackError> {
+        if self.cards.is_empty() {
+            self.reshuffle();
+        }
+        self.cards.pop().ok_or(BlackjackError::EmptyShoe)
+    }
+
+    pub(crate) fn discard(&mut self, cards: &[Card]) {
+        self.discards.extend_from_slice(cards);
+    }
+
+    /// Cards left to deal.
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Hi-Lo running count of the cards in the discard tray.
+    pub fn running_count(&self) -> i32 {
+        self.count(CountingSystem::HiLo)
+    }
+
+    /// Running count of the discard tray with any counting system.
+    pub fn count(&self, system: CountingSystem) -> i32 {
+        self.discards.iter().map(|c| system.tag(*c)).sum()
+    }
+
+    /// Running count divided by the decks left to deal.
+    pub fn true_count(&self, system: CountingSystem) -> f32 {
+        let decks: f32 = (self.cards.len() as f32 / self.deck_size as f32).max(0.5);
+        self.count(system) as f32 / decks
+    }
+
+    /// Cards dealt since the last shuffle.
+    pub fn position(&self) -> usize {
+        self.size - self.cards.len()
+    }
+
+    /// Seed every shuffle of the shoe is drawn from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Times the shoe has been shuffled, the current shuffle being the last one.
+    pub fn shuffles(&self) -> u64 {
+        self.shuffles
+    }
+
+    /// Cards the last mid round reshuffle left out of the shoe.
+    pub fn held_back(&self) -> &[Card] {
+        &self.held_back
+    }
+
+    /// The cut card has been reached.
+    pub fn needs_shuffle(&self) -> bool {
+        self.position() as f32 >= self.size as f32 * self.penetration
+    }
+
+    /// Shuffles the discard tray back in while cards are still on the table.
+    fn reshuffle(&mut self) {
+        let mut held_back: Vec<Card> = self.fresh_cards();
+        for card in self.cards.iter().chain(self.discards.iter()) {
+            if let Some(index) = held_back.iter().position(|c| c == card) {
+                held_back.swap_remove(index);
+            }
+        }
+        held_back.sort();
+        self.held_back = held_back;
+        self.discards.sort();
+        self.cards.append(&mut self.discards);
+        let mut rng: StdRng = self.rng();
+        self.cards.shuffle(&mut rng);
+        self.shuffles += 1;
+    }
+}
+
+/// Card counting systems the player can have the count shown in.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CountingSystem {
+    #[default]
+    HiLo,
+    /// Knock-out, Hi-Lo with the sevens counted as low cards.
+    Ko,
+    HiOptI,
+    #[serde(rename = "omega-ii")]
+    OmegaII,
+}
+
+impl CountingSystem {
+    /// Value the system gives to a card leaving the shoe.
+    pub fn tag(&self, card: Card) -> i32 {
+        match (self, card.value) {
+            (CountingSystem::HiLo, 2..=6) => 1,
+            (CountingSystem::HiLo, 7..=9) => 0,
+            (CountingSystem::Ko, 2..=7) => 1,
+            (CountingSystem::Ko, 8..=9) => 0,
+            (CountingSystem::HiOptI, 3..=6) => 1,
+            (CountingSystem::HiOptI, 10) => -1,
+            (CountingSystem::HiOptI, _) => 0,
+            (CountingSystem::OmegaII, 2 | 3 | 7) => 1,
+            (CountingSystem::OmegaII, 4..=6) => 2,
+            (CountingSystem::OmegaII, 9) => -1,
+            (CountingSystem::OmegaII, 10) => -2,
+            (CountingSystem::OmegaII, _) => 0,
+            _ => -1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant;
+
+    #[test]
+    fn true_count_divides_by_the_decks_of_the_variant() {
+        let rules: Rules = Rules { variant: Variant::Spanish21, decks: 2, ..Rules::default() };
+        let deck: Deck = Deck::at(&rules, 3, 1, 48, &[]);
+        assert_eq!(deck.remaining(), 48);
+        assert_eq!(deck.true_count(CountingSystem::HiLo), deck.running_count() as f32);
+    }
+}
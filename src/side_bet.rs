@@ -0,0 +1,448 @@
+use crate::card::Card;
+use crate::hand::HandValue;
+use crate::poker::{three_card_hand, PokerHand};
+use crate::rules::Rules;
+use serde::{Deserialize, Serialize};
+
+/// Side bets that can be placed next to the main bet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SideBetKind {
+    /// The player's first two cards make a pair.
+    PerfectPairs,
+    /// The player's first two cards and the dealer's up card make a three card poker hand.
+    #[serde(rename = "21+3")]
+    TwentyOnePlusThree,
+    /// The player's first two cards total 20.
+    LuckyLadies,
+    /// The dealer busts, paid by the number of cards in the busted hand.
+    BusterBlackjack,
+    /// The player's first two cards match the rank of the dealer's up card.
+    MatchTheDealer,
+}
+
+impl SideBetKind {
+    /// Every side bet, in the order they are offered.
+    pub const ALL: [SideBetKind; 5] = [
+        SideBetKind::PerfectPairs,
+        SideBetKind::TwentyOnePlusThree,
+        SideBetKind::LuckyLadies,
+        SideBetKind::BusterBlackjack,
+        SideBetKind::MatchTheDealer,
+    ];
+
+    /// Name shown to the player.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SideBetKind::PerfectPairs => "Perfect Pairs",
+            SideBetKind::TwentyOnePlusThree => "21+3",
+            SideBetKind::LuckyLadies => "Lucky Ladies",
+            SideBetKind::BusterBlackjack => "Buster Blackjack",
+            SideBetKind::MatchTheDealer => "Match the Dealer",
+        }
+    }
+
+    /// Name used on the command line and in configuration files.
+    pub fn id(&self) -> &'static str {
+        match self {
+            SideBetKind::PerfectPairs => "perfect-pairs",
+            SideBetKind::TwentyOnePlusThree => "21+3",
+            SideBetKind::LuckyLadies => "lucky-ladies",
+            SideBetKind::BusterBlackjack => "buster-blackjack",
+            SideBetKind::MatchTheDealer => "match-the-dealer",
+        }
+    }
+
+    /// Side bet of an `id`, `None` for any other name.
+    pub fn from_id(id: &str) -> Option<Self> {
+        SideBetKind::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+}
+
+/// When a side bet can be decided.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SideBetTiming {
+    /// Right after the deal.
+    Deal,
+    /// Once the dealer's hand is finished, the dealer then plays it out even when every hand is settled.
+    DealerTurn,
+}
+
+/// Dealer cards a side bet is decided on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DealerCards {
+    /// Only the player's cards count.
+    NoCards,
+    /// The dealer's up card counts with the player's first two cards.
+    UpCard,
+    /// Only the values of the first two cards count.
+    FirstTwo,
+    /// Only card values count: the dealer's finished hand and the player's first two, in either order.
+    FinishedHand,
+}
+
+/// A side bet paytable, deciding what the bet pays from the cards of the round.
+pub trait SideBet {
+    /// Dealer cards the bet is decided on.
+    fn dealer_cards(&self) -> DealerCards {
+        DealerCards::UpCard
+    }
+
+    /// When the dealer cards the bet is decided on are known.
+    fn timing(&self, rules: &Rules) -> SideBetTiming {
+        match self.dealer_cards() {
+            DealerCards::NoCards | DealerCards::UpCard => SideBetTiming::Deal,
+            DealerCards::FirstTwo if rules.has_hole_card() => SideBetTiming::Deal,
+            _ => SideBetTiming::DealerTurn,
+        }
+    }
+
+    /// Odds paid to one on the player's first two cards and the dealer's cards at the bet's timing,
+    /// `None` when the bet loses.
+    fn odds(&self, player: &[Card], dealer: &[Card]) -> Option<u32>;
+}
+
+/// How the first two cards pair up, from the weakest to the best paid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PairKind {
+    /// Same rank in a red and a black suit.
+    Mixed,
+    /// Same rank and colour, different suits.
+    Colored,
+    /// Same rank and suit.
+    Perfect,
+}
+
+fn is_red(card: Card) -> bool {
+    card.suit == '\u{2665}' || card.suit == '\u{2666}'
+}
+
+/// Kind of pair the two cards make, if they share a rank.
+pub fn pair_kind(first: Card, second: Card) -> Option<PairKind> {
+    if first.rank != second.rank {
+        None
+    } else if first.suit == second.suit {
+        Some(PairKind::Perfect)
+    } else if is_red(first) == is_red(second) {
+        Some(PairKind::Colored)
+    } else {
+        Some(PairKind::Mixed)
+    }
+}
+
+/// Perfect Pairs paytable, each entry as odds to one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PerfectPairs {
+    pub mixed: u32,
+    pub colored: u32,
+    pub perfect: u32,
+}
+
+impl Default for PerfectPairs {
+    fn default() -> Self {
+        PerfectPairs { mixed: 6, colored: 12, perfect: 25 }
+    }
+}
+
+impl SideBet for PerfectPairs {
+    fn dealer_cards(&self) -> DealerCards {
+        DealerCards::NoCards
+    }
+
+    fn odds(&self, player: &[Card], _dealer: &[Card]) -> Option<u32> {
+        match player {
+            [first, second, ..] => pair_kind(*first, *second).map(|kind| match kind {
+                PairKind::Mixed => self.mixed,
+                PairKind::Colored => self.colored,
+                PairKind::Perfect => self.perfect,
+            }),
+            _ => None
+        }
+    }
+}
+
+/// 21+3 paytable, each entry as odds to one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TwentyOnePlusThree {
+    pub flush: u32,
+    pub straight: u32,
+    pub three_of_a_kind: u32,
+    pub straight_flush: u32,
+    pub suited_trips: u32,
+}
+
+impl Default for TwentyOnePlusThree {
+    fn default() -> Self {
+        TwentyOnePlusThree { flush: 5, straight: 10, three_of_a_kind: 30, straight_flush: 40, suited_trips: 100 }
+    }
+}
+
+impl SideBet for TwentyOnePlusThree {
+    fn odds(&self, player: &[Card], dealer: &[Card]) -> Option<u32> {
+        match (player, dealer) {
+            ([first, second, ..], [up_card, ..]) => three_card_hand([*first, *second, *up_card]).map(|hand| match hand {
+                PokerHand::Flush => self.flush,
+                PokerHand::Straight => self.straight,
+                PokerHand::ThreeOfAKind => self.three_of_a_kind,
+                PokerHand::StraightFlush => self.straight_flush,
+                PokerHand::SuitedTrips => self.suited_trips,
+            }),
+            _ => None
+        }
+    }
+}
+
+fn is_queen_of_hearts(card: Card) -> bool {
+    card.rank == 'Q' && card.suit == '\u{2665}'
+}
+
+/// Lucky Ladies paytable, each entry as odds to one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LuckyLadies {
+    pub any_20: u32,
+    pub suited_20: u32,
+    /// Same rank and suit.
+    pub matched_20: u32,
+    pub queens_of_hearts: u32,
+    /// Both queens of hearts against a dealer blackjack.
+    pub queens_of_hearts_dealer_blackjack: u32,
+}
+
+impl Default for LuckyLadies {
+    fn default() -> Self {
+        LuckyLadies {
+            any_20: 4,
+            suited_20: 10,
+            matched_20: 25,
+            queens_of_hearts: 200,
+            queens_of_hearts_dealer_blackjack: 1000,
+        }
+    }
+}
+
+impl SideBet for LuckyLadies {
+    /// Without a hole card the dealer's blackjack is only known once the dealer plays.
+    fn dealer_cards(&self) -> DealerCards {
+        DealerCards::FirstTwo
+    }
+
+    fn odds(&self, player: &[Card], dealer: &[Card]) -> Option<u32> {
+        let [first, second, ..] = *player else {
+            return None;
+        };
+        if HandValue::new(&[first, second], false).total != 20 {
+            None
+        } else if is_queen_of_hearts(first) && is_queen_of_hearts(second) {
+            let dealer_blackjack: bool = dealer.len() >= 2 && HandValue::new(&dealer[..2], false).blackjack;
+            Some(if dealer_blackjack { self.queens_of_hearts_dealer_blackjack } else { self.queens_of_hearts })
+        } else if first.rank == second.rank && first.suit == second.suit {
+            Some(self.matched_20)
+        } else if first.suit == second.suit {
+            Some(self.suited_20)
+        } else {
+            Some(self.any_20)
+        }
+    }
+}
+
+/// Buster Blackjack paytable by the number of cards in the dealer's busted hand, each entry as odds to one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BusterBlackjack {
+    pub three_cards: u32,
+    pub four_cards: u32,
+    pub five_cards: u32,
+    pub six_cards: u32,
+    pub seven_cards: u32,
+    /// Eight cards or more.
+    pub eight_cards: u32,
+}
+
+impl Default for BusterBlackjack {
+    fn default() -> Self {
+        BusterBlackjack { three_cards: 1, four_cards: 2, five_cards: 9, six_cards: 50, seven_cards: 100, eight_cards: 250 }
+    }
+}
+
+impl SideBet for BusterBlackjack {
+    fn dealer_cards(&self) -> DealerCards {
+        DealerCards::FinishedHand
+    }
+
+    fn odds(&self, _player: &[Card], dealer: &[Card]) -> Option<u32> {
+        if !HandValue::new(dealer, false).bust {
+            return None;
+        }
+        match dealer.len() {
+            0..=2 => None,
+            3 => Some(self.three_cards),
+            4 => Some(self.four_cards),
+            5 => Some(self.five_cards),
+            6 => Some(self.six_cards),
+            7 => Some(self.seven_cards),
+            _ => Some(self.eight_cards),
+        }
+    }
+}
+
+/// Match the Dealer paytable, paid for each of the player's first two cards matching the dealer's up card,
+/// each entry as odds to one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MatchTheDealer {
+    /// Same rank, different suit.
+    pub unsuited: u32,
+    /// Same rank and suit.
+    pub suited: u32,
+}
+
+impl Default for MatchTheDealer {
+    fn default() -> Self {
+        MatchTheDealer { unsuited: 4, suited: 11 }
+    }
+}
+
+impl SideBet for MatchTheDealer {
+    fn odds(&self, player: &[Card], dealer: &[Card]) -> Option<u32> {
+        let up_card: Card = *dealer.first()?;
+        let odds: u32 = player
+            .iter()
+            .take(2)
+            .filter(|c| c.rank == up_card.rank)
+            .map(|c| if c.suit == up_card.suit { self.suited } else { self.unsuited })
+            .sum();
+        if odds > 0 { Some(odds) } else { None }
+    }
+}
+
+/// Paytables of the side bets offered at the table.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SideBets {
+    pub perfect_pairs: PerfectPairs,
+    #[serde(rename = "21+3")]
+    pub twenty_one_plus_three: TwentyOnePlusThree,
+    pub lucky_ladies: LuckyLadies,
+    pub buster_blackjack: BusterBlackjack,
+    pub match_the_dealer: MatchTheDealer,
+}
+
+impl SideBets {
+    /// Paytable of the `kind` side bet.
+    pub fn get(&self, kind: SideBetKind) -> &dyn SideBet {
+        match kind {
+            SideBetKind::PerfectPairs => &self.perfect_pairs,
+            SideBetKind::TwentyOnePlusThree => &self.twenty_one_plus_three,
+            SideBetKind::LuckyLadies => &self.lucky_ladies,
+            SideBetKind::BusterBlackjack => &self.buster_blackjack,
+            SideBetKind::MatchTheDealer => &self.match_the_dealer,
+        }
+    }
+}
+
+/// A side bet placed on a round and what it paid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SideBetResult {
+    pub kind: SideBetKind,
+    pub wager: u32,
+    /// Amount given back to the player, stake included, 0 when the bet lost.
+    pub payout: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PeekRule;
+
+    /// Cards written as rank and suit letter, `s`, `h`, `c` or `d`.
+    fn cards(cards: &str) -> Vec<Card> {
+        cards
+            .split_whitespace()
+            .map(|c| {
+                let mut chars = c.chars();
+                let rank: char = chars.next().unwrap();
+                let suit: char = match chars.next().unwrap() {
+                    's' => '\u{2660}',
+                    'h' => '\u{2665}',
+                    'c' => '\u{2663}',
+                    _ => '\u{2666}',
+                };
+                Card::of(rank, suit)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn perfect_pairs_pays_by_the_kind_of_pair() {
+        let table: PerfectPairs = PerfectPairs::default();
+        assert_eq!(table.odds(&cards("8s 8h"), &[]), Some(6));
+        assert_eq!(table.odds(&cards("8h 8d"), &[]), Some(12));
+        assert_eq!(table.odds(&cards("8c 8c"), &[]), Some(25));
+        assert_eq!(table.odds(&cards("8c 9c"), &[]), None);
+        // Tens and faces are worth the same but don't pair.
+        assert_eq!(table.odds(&cards("Ts Ks"), &[]), None);
+        assert_eq!(table.dealer_cards(), DealerCards::NoCards);
+    }
+
+    #[test]
+    fn side_bets_read_their_ids_back() {
+        for kind in SideBetKind::ALL {
+            assert_eq!(SideBetKind::from_id(kind.id()), Some(kind));
+        }
+        assert_eq!(SideBetKind::from_id("perfect pairs"), None);
+    }
+
+    #[test]
+    fn twenty_one_plus_three_plays_the_dealer_up_card() {
+        let table: TwentyOnePlusThree = TwentyOnePlusThree::default();
+        assert_eq!(table.odds(&cards("9h Th"), &cards("Jh 4c")), Some(40));
+        assert_eq!(table.odds(&cards("9h Tc"), &cards("Jh")), Some(10));
+        assert_eq!(table.odds(&cards("9h 9c"), &cards("Jh")), None);
+        // Cards drawn later don't count.
+        assert_eq!(table.odds(&cards("9h 2c 7d"), &cards("8h")), None);
+        assert_eq!(table.odds(&cards("9h Th"), &[]), None);
+        assert_eq!(table.dealer_cards(), DealerCards::UpCard);
+    }
+
+    #[test]
+    fn lucky_ladies_pays_a_20_by_its_suits() {
+        let table: LuckyLadies = LuckyLadies::default();
+        assert_eq!(table.odds(&cards("Ks Tc Ah"), &[]), Some(4));
+        assert_eq!(table.odds(&cards("Ks Js"), &[]), Some(10));
+        assert_eq!(table.odds(&cards("Ks Ks"), &[]), Some(25));
+        assert_eq!(table.odds(&cards("Qh Qh"), &cards("9s")), Some(200));
+        assert_eq!(table.odds(&cards("Qh Qh"), &cards("As Ks")), Some(1000));
+        assert_eq!(table.odds(&cards("Ks 8c"), &[]), None);
+    }
+
+    #[test]
+    fn buster_blackjack_pays_by_the_cards_of_the_busted_dealer() {
+        let table: BusterBlackjack = BusterBlackjack::default();
+        assert_eq!(table.odds(&[], &cards("Ts 6h 8c")), Some(1));
+        assert_eq!(table.odds(&[], &cards("2s 3h 4c 5d 6s 7h")), Some(50));
+        assert_eq!(table.odds(&[], &cards("As As 2s 2h 3c 3d 4s 9h")), Some(250));
+        assert_eq!(table.odds(&[], &cards("Ts 6h 5c")), None);
+    }
+
+    #[test]
+    fn match_the_dealer_pays_each_matching_card() {
+        let table: MatchTheDealer = MatchTheDealer::default();
+        assert_eq!(table.odds(&cards("7s 7h"), &cards("7s")), Some(15));
+        assert_eq!(table.odds(&cards("7c 7h"), &cards("7s")), Some(8));
+        assert_eq!(table.odds(&cards("7c Th"), &cards("7s")), Some(4));
+        assert_eq!(table.odds(&cards("Kc Th"), &cards("Js")), None);
+    }
+
+    #[test]
+    fn bets_on_the_dealer_hand_wait_for_the_dealer() {
+        let rules: Rules = Rules::default();
+        let enhc: Rules = Rules { peek: PeekRule::NoHoleCard, ..Rules::default() };
+        assert_eq!(LuckyLadies::default().timing(&rules), SideBetTiming::Deal);
+        assert_eq!(LuckyLadies::default().timing(&enhc), SideBetTiming::DealerTurn);
+        assert_eq!(BusterBlackjack::default().timing(&rules), SideBetTiming::DealerTurn);
+        assert_eq!(MatchTheDealer::default().timing(&enhc), SideBetTiming::Deal);
+    }
+}
@@ -0,0 +1,789 @@
+use crate::card::Card;
+use crate::error::BlackjackError;
+use crate::hand::{Hand, HandValue};
+use crate::round::Decision;
+use crate::rules::{PeekRule, Rules, Variant};
+use std::fs;
+
+/// Hard totals of the basic strategy table.
+fn basic_strategy_hard_totals(hand: &Hand, face_up_card: Option<Card>) -> Decision {
+    let player_value = hand.value().total;
+    if let Some(card) = face_up_card {
+        match card.rank {
+            '2' => { 
+                if player_value >= 13 { 
+                    Decision::Stand 
+                } else if player_value == 12 || player_value <= 9 {
+                    Decision::Hit
+                } else {
+                    Decision::Double
+                }
+            },
+            '3' => {
+                if player_value >= 13 {
+                    Decision::Stand
+                } else if player_value == 12 || player_value <= 8 {
+                    Decision::Hit
+                } else {
+                    Decision::Double
+                }
+            },
+            '4' | '5' | '6' => {
+                if player_value >= 12 {
+                    Decision::Stand
+                } else if player_value <= 8 {
+                    Decision::Hit
+                } else {
+                    Decision::Double
+                }
+            },
+            '7' | '8' | '9' => {
+                if player_value >= 17 {
+                    Decision::Stand
+                } else if player_value == 11 || player_value == 10 {
+                    Decision::Double
+                } else {
+                    Decision::Hit
+                }
+            },
+            _ => {
+                if player_value >= 17 {
+                    Decision::Stand
+                } else if player_value == 11 {
+                    Decision::Double
+                } else {
+                    Decision::Hit
+                }
+            }
+        }
+    } else {
+        Decision::None
+    }
+}
+
+/// Two card soft totals, by the card next to the ace.
+fn basic_strategy_soft_totals(hand: &Hand, face_up_card: Option<Card>) -> Decision {
+    let other_card: Option<Card> = hand.soft_kicker();
+    if let Some(dealer_card) = face_up_card {
+        match dealer_card.rank {
+            '2' => {
+                if let Some(card) = other_card {
+                    if card.value > 7 {
+                        Decision::Stand
+                    } else if card.value == 7 {
+                        Decision::Double
+                    } else {
+                        Decision::Hit
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '3' => {
+                if let Some(card) = other_card {
+                    if card.value > 7 {
+                        Decision::Stand
+                    } else if card.value > 5 {
+                        Decision::Double
+                    } else {
+                        Decision::Hit
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '4' => {
+                if let Some(card) = other_card {
+                    if card.value > 7 {
+                        Decision::Stand
+                    } else if card.value > 3 {
+                        Decision::Double
+                    } else {
+                        Decision::Hit
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '5' => {
+                if let Some(card) = other_card {
+                    if card.value > 7 {
+                        Decision::Stand
+                    } else {
+                        Decision::Hit
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '6' => {
+                if let Some(card) = other_card {
+                    if card.value == 9 {
+                        Decision::Stand
+                    } else {
+                        Decision::Double
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '7' | '8' => {
+                if let Some(card) = other_card {
+                    if card.value > 6 {
+                        Decision::Stand
+                    } else {
+                        Decision::Hit
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            _ => {
+                if let Some(card) = other_card {
+                    if card.value > 7 {
+                        Decision::Stand
+                    } else {
+                        Decision::Hit
+                    }
+                } else {
+                    Decision::None
+                }
+            }
+        }    
+    } else {
+        Decision::None
+    }
+}
+
+/// Pairs, by the rank of the pair.
+fn basic_strategy_pairs(hand: &Hand, face_up_card: Option<Card>) -> Decision {
+    let other_card: Option<Card> = hand.cards.first().copied();
+    if let Some(dealer_card) = face_up_card {
+        match dealer_card.rank {
+            '2' | '3' | '4' => {
+                if let Some(card) = other_card {
+                    match card.value {
+                        10 => Decision::Stand,
+                        5 => Decision::Double,
+                        4 => Decision::Hit,
+                        _ => Decision::Split
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '5' | '6' => {
+                if let Some(card) = other_card {
+                    match card.value {
+                        10 => Decision::Stand,
+                        5 => Decision::Double,
+                        _ => Decision::Split
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '7' => {
+                if let Some(card) = other_card {
+                    match card.value {
+                        10 | 9 => Decision::Stand,
+                        6 | 4 => Decision::Hit,
+                        5 => Decision::Double,
+                        _ => Decision::Split
+                    }
+                } else {
+                    Decision::None
+                }
+            },
+            '8' | '9' => {
+                if let Some(card) = other_card {
+                    match card.value {
+                        10 => Decision::Stand,
+                        5 => Decision::Double,
+                        7 | 6 | 4 | 3 | 2 => Decision::Hit,
+                        _ => Decision::Split
+                    }
+                } else {
+                    Decision::None
+                }
+            }
+            _ => {
+                if let Some(card) = other_card {
+                    match card.value {
+                        10 | 9 => Decision::Stand,
+                        1 | 8 => Decision::Split,
+                        _ => Decision::Hit
+                    }
+                } else {
+                    Decision::None
+                }
+            }
+        }
+    } else {
+        Decision::None
+    }
+}
+
+/// Basic strategy decision for a hand against the dealer's up card, which [`Chart::basic`] is built from.
+fn basic_strategy(hand: &Hand, face_up_card: Option<Card>) -> Decision {
+    let value: HandValue = hand.value();
+    if hand.cards.len() == 2 {
+        if value.pair.is_some() {
+            basic_strategy_pairs(hand, face_up_card)
+        } else if value.soft {
+            basic_strategy_soft_totals(hand, face_up_card)
+        } else {
+            basic_strategy_hard_totals(hand, face_up_card)
+        }
+    } else {
+        basic_strategy_hard_totals(hand, face_up_card)
+    }
+}
+
+/// Something that takes decisions for the player.
+pub trait Strategy {
+    /// Name shown in reports and when grading decisions.
+    fn name(&self) -> &str;
+
+    /// Picks one of the `legal` decisions for `hand` against the dealer's visible
+    /// cards, the up card first.
+    fn decide(&self, hand: &Hand, dealer: &[Card], legal: &[Decision]) -> Decision;
+
+    /// Blackjack Switch: swap the second cards of the two hands?
+    /// By default the pair of hands that starts out stronger is kept.
+    fn switch(&self, first: &Hand, second: &Hand, _dealer: &[Card]) -> bool {
+        let (a, b) = (first.cards(), second.cards());
+        let switched: f64 = starting_score(&[a[0], b[1]], true) + starting_score(&[b[0], a[1]], true);
+        switched > starting_score(a, false) + starting_score(b, false)
+    }
+}
+
+/// Rough expected value of a starting hand against an average up card.
+/// A switched hand's 21 isn't a natural and pushes a dealer 22.
+fn starting_score(cards: &[Card], switched: bool) -> f64 {
+    let value: HandValue = HandValue::new(cards, switched);
+    match (value.pair, value.soft, value.total) {
+        _ if value.blackjack => 1.0,
+        (_, _, 21) => 0.85,
+        (Some('A'), _, _) => 0.25,
+        (Some('8'), _, _) => -0.1,
+        (_, true, 20) => 0.45,
+        (_, true, 19) => 0.25,
+        (_, true, 18) => 0.0,
+        (_, true, _) => -0.05,
+        (_, false, 20) => 0.55,
+        (_, false, 19) => 0.28,
+        (_, false, 18) => 0.05,
+        (_, false, 17) => -0.15,
+        (_, false, total @ 12..=16) => -0.25 - 0.05 * (total - 12) as f64,
+        (_, false, 11) => 0.25,
+        (_, false, 10) => 0.18,
+        (_, false, 9) => 0.05,
+        (_, false, 8) => -0.05,
+        _ => -0.12,
+    }
+}
+
+/// Spanish 21 basic strategy, from the two card decisions of the usual charts.
+const SPANISH_21: &str = "\
+hard 9: H H H H D H H H H H
+hard 10: D D D D D D H H H H
+hard 11: D D D D D D D D H H
+hard 12: H H H S S H H H H H
+hard 13: H H S S S H H H H H
+hard 14: S S S S S H H H H H
+hard 15: S S S S S H H H H H
+hard 16: S S S S S H H H H H
+hard 17: S S S S S S S S S R
+soft 13: H H H H D H H H H H
+soft 14: H H H H D H H H H H
+soft 15: H H H D D H H H H H
+soft 16: H H H D D H H H H H
+soft 17: H H D D D H H H H H
+soft 18: S S D D D S S H H H
+pair A: P P P P P P P P P P
+pair 2: P P P P P P H H H H
+pair 3: P P P P P P P H H H
+pair 4: H H H H H H H H H H
+pair 5: D D D D D D H H H H
+pair 6: H H P P P H H H H H
+pair 7: P P P P P P H H H H
+pair 8: P P P P P P P P P P
+pair 9: S P P P P S P P S S
+pair T: S S S S S S S S S S
+";
+
+/// Blackjack Switch basic strategy.
+const SWITCH: &str = "\
+hard 9: H H H H H H H H H H
+hard 10: H D D D D H H H H H
+hard 11: D D D D D D D H H H
+hard 12: H H H H H H H H H H
+hard 13: H H H S S H H H H H
+hard 14: H H S S S H H H H H
+hard 15: S S S S S H H H H H
+hard 16: S S S S S H H H H H
+soft 13: H H H H H H H H H H
+soft 14: H H H H H H H H H H
+soft 15: H H H H H H H H H H
+soft 16: H H H H D H H H H H
+soft 17: H H H D D H H H H H
+soft 18: S S S D D S S H H H
+pair A: P P P P P P P P P P
+pair 2: H H P P P P H H H H
+pair 3: H H P P P P H H H H
+pair 4: H H H H H H H H H H
+pair 5: H D D D D H H H H H
+pair 6: H H P P P H H H H H
+pair 7: P P P P P P H H H H
+pair 8: P P P P P P P P H H
+pair 9: S P P P P S P P S S
+pair T: S S S S S S S S S S
+";
+
+/// Free Bet basic strategy: every free double and split is taken, and the
+/// dealer 22 push takes the value out of paid doubles.
+const FREE_BET: &str = "\
+hard 9: D D D D D D D D D D
+hard 10: D D D D D D D D D D
+hard 11: D D D D D D D D D D
+soft 13: H H H H D H H H H H
+soft 14: H H H H D H H H H H
+soft 15: H H H D D H H H H H
+soft 16: H H H D D H H H H H
+soft 17: H H D D D H H H H H
+soft 18: S S D D D S S H H H
+pair A: P P P P P P P P P P
+pair 2: P P P P P P P P P P
+pair 3: P P P P P P P P P P
+pair 4: P P P P P P P P P P
+pair 5: D D D D D D D D D D
+pair 6: P P P P P P P P P P
+pair 7: P P P P P P P P P P
+pair 8: P P P P P P P P P P
+pair 9: P P P P P P P P P P
+pair T: S S S S S S S S S S
+";
+
+const UP_CARDS: [char; 10] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'A'];
+
+/// The up card a fully exposed dealer hand plays like: its total when it's 11
+/// or less, an ace for the soft hands that can still draw.
+fn exposed_card(dealer: &HandValue) -> Card {
+    match dealer.total {
+        2..=10 if dealer.is_hard() => Card::of(rank_of(dealer.total), '\u{2660}'),
+        _ => Card::of('A', '\u{2660}'),
+    }
+}
+
+/// Double Exposure play against a dealer hand that is made or hard 12 to 16.
+/// `None` leaves the hand to the chart.
+fn exposed_decision(hand: &Hand, dealer: &HandValue, legal: &[Decision]) -> Option<Decision> {
+    let value: HandValue = hand.value();
+    match dealer.total {
+        // Ties lose, so anything short of beating the dealer draws.
+        17..=21 => Some(if value.total > dealer.total { Decision::Stand } else { Decision::Hit }),
+        12..=16 if dealer.is_hard() => {
+            let stiff: bool = dealer.total >= 13;
+            let decision: Decision = match value.pair {
+                Some(rank) if stiff && rank != '5' && rank != 'T' && legal.contains(&Decision::Split) => Decision::Split,
+                _ if value.soft => match value.total {
+                    13..=19 if stiff && legal.contains(&Decision::Double) => Decision::Double,
+                    18..=21 => Decision::Stand,
+                    _ => Decision::Hit,
+                },
+                _ => match value.total {
+                    12..=21 => Decision::Stand,
+                    5..=8 if !stiff => Decision::Hit,
+                    _ => Decision::Double,
+                },
+            };
+            Some(decision)
+        },
+        _ => None,
+    }
+}
+
+/// Pontoon play with nothing of the dealer's showing: buy on 9 to 11, chase
+/// the five card trick from four cards and otherwise stick on hard 16 or soft 19.
+fn pontoon_decision(hand: &Hand, legal: &[Decision]) -> Decision {
+    let value: HandValue = hand.value();
+    let cards: usize = hand.cards().len();
+    if value.pair == Some('A') && legal.contains(&Decision::Split) {
+        Decision::Split
+    } else if cards == 2 && value.is_hard() && (9..=11).contains(&value.total) && legal.contains(&Decision::Double) {
+        Decision::Double
+    } else if cards >= 4 {
+        if value.total >= 18 { Decision::Stand } else { Decision::Hit }
+    } else if value.total >= 19 || (value.is_hard() && value.total >= 16) {
+        Decision::Stand
+    } else {
+        Decision::Hit
+    }
+}
+
+/// Column of a dealer up card in a chart.
+fn column(up_card: Card) -> usize {
+    if up_card.value() == 1 { 9 } else { (up_card.value() - 2) as usize }
+}
+
+fn letter(decision: Decision) -> char {
+    match decision {
+        Decision::Hit => 'H',
+        Decision::Stand => 'S',
+        Decision::Double => 'D',
+        Decision::Split => 'P',
+        Decision::Surrender => 'R',
+        Decision::None => '-',
+    }
+}
+
+fn from_letter(letter: &str) -> Option<Decision> {
+    match letter {
+        "H" => Some(Decision::Hit),
+        "S" => Some(Decision::Stand),
+        "D" => Some(Decision::Double),
+        "P" => Some(Decision::Split),
+        "R" => Some(Decision::Surrender),
+        _ => None
+    }
+}
+
+/// A strategy chart: one decision per player hand and dealer up card.
+///
+/// Charts are written one row per line, `hard 16: S S S S S H H H H H`,
+/// with a column for each up card from 2 to ace. Hard rows go from 5 to 21,
+/// soft rows from 13 to 21 and pair rows are named by rank (`pair A`, `pair 8`).
+/// The letters are H, S, D, P and R for surrender.
+#[derive(Clone)]
+pub struct Chart {
+    name: String,
+    hard: [[Decision; 10]; 22],
+    soft: [[Decision; 10]; 22],
+    /// Indexed by the value of the paired card.
+    pairs: [[Decision; 10]; 11],
+}
+
+impl Chart {
+    /// The chart followed by the basic strategy functions.
+    pub fn basic() -> Self {
+        let mut chart: Chart = Chart {
+            name: "basic".to_string(),
+            hard: [[Decision::Stand; 10]; 22],
+            soft: [[Decision::Stand; 10]; 22],
+            pairs: [[Decision::Stand; 10]; 11],
+        };
+        for (i, rank) in UP_CARDS.iter().enumerate() {
+            let up_card: Option<Card> = Some(Card::of(*rank, '\u{2660}'));
+            for total in 5..=21u8 {
+                // Two cards that don't pair up and hold no ace, or three for the big totals.
+                let cards: Vec<Card> = match total {
+                    5..=11 => vec![Card::of('2', '\u{2660}'), Card::of(rank_of(total - 2), '\u{2665}')],
+                    12..=19 => vec![Card::of('T', '\u{2660}'), Card::of(rank_of(total - 10), '\u{2665}')],
+                    _ => vec![Card::of('T', '\u{2660}'), Card::of('5', '\u{2665}'), Card::of(rank_of(total - 15), '\u{2663}')],
+                };
+                chart.hard[total as usize][i] = basic_strategy(&Hand::from_cards(cards), up_card);
+            }
+            for total in 13..=21u8 {
+                let cards: Vec<Card> = vec![Card::of('A', '\u{2660}'), Card::of(rank_of(total - 11), '\u{2665}')];
+                chart.soft[total as usize][i] = basic_strategy(&Hand::from_cards(cards), up_card);
+            }
+            for value in 1..=10u8 {
+                let cards: Vec<Card> = vec![Card::of(rank_of(value), '\u{2660}'), Card::of(rank_of(value), '\u{2665}')];
+                chart.pairs[value as usize][i] = basic_strategy(&Hand::from_cards(cards), up_card);
+            }
+        }
+        chart
+    }
+
+    /// Basic strategy for Spanish 21 with late surrender, dealer standing on soft 17.
+    pub fn spanish21() -> Self {
+        let mut chart: Chart = Chart::basic();
+        chart.name = "spanish21".to_string();
+        chart
+            .read("spanish21", SPANISH_21)
+            .expect("the Spanish 21 chart is well formed");
+        chart
+    }
+
+    /// Blackjack Switch basic strategy, playing tighter because of the dealer 22 push.
+    pub fn switch() -> Self {
+        let mut chart: Chart = Chart::basic();
+        chart.name = "switch".to_string();
+        chart
+            .read("switch", SWITCH)
+            .expect("the Blackjack Switch chart is well formed");
+        chart
+    }
+
+    /// Double Exposure falls back on basic strategy only when the dealer's total
+    /// is low or soft, `decide` plays the made and stiff dealer hands.
+    pub fn double_exposure() -> Self {
+        Chart {
+            name: "double-exposure".to_string(),
+            ..Chart::basic()
+        }
+    }
+
+    /// Free Bet basic strategy, taking every free double and split.
+    pub fn free_bet() -> Self {
+        let mut chart: Chart = Chart::basic();
+        chart.name = "free-bet".to_string();
+        chart
+            .read("free-bet", FREE_BET)
+            .expect("the Free Bet chart is well formed");
+        chart
+    }
+
+    /// Pontoon shows no dealer card to read a chart with, `decide` plays it on
+    /// the player's cards alone.
+    pub fn pontoon() -> Self {
+        Chart {
+            name: "pontoon".to_string(),
+            ..Chart::basic()
+        }
+    }
+
+    /// ENHC: a dealer blackjack also takes doubles and splits, so nothing is
+    /// doubled against a ten or an ace and only aces against a ten are split.
+    fn no_hole_card(mut self) -> Self {
+        self.name = format!("{}-enhc", self.name);
+        let ten: usize = column(Card::of('T', '\u{2660}'));
+        for column in [ten, column(Card::of('A', '\u{2660}'))] {
+            for row in self.hard.iter_mut().chain(self.soft.iter_mut()) {
+                if row[column] == Decision::Double {
+                    row[column] = Decision::Hit;
+                }
+            }
+            for (value, row) in self.pairs.iter_mut().enumerate() {
+                let aces_against_ten: bool = value == 1 && column == ten;
+                if matches!(row[column], Decision::Double | Decision::Split) && !aces_against_ten {
+                    row[column] = Decision::Hit;
+                }
+            }
+        }
+        self
+    }
+
+    /// The built-in chart for the game the rules describe.
+    pub fn for_rules(rules: &Rules) -> Self {
+        let chart: Chart = match rules.variant {
+            Variant::Classic => Chart::basic(),
+            Variant::Spanish21 => Chart::spanish21(),
+            Variant::Switch => Chart::switch(),
+            Variant::DoubleExposure => Chart::double_exposure(),
+            Variant::FreeBet => Chart::free_bet(),
+            Variant::Pontoon => Chart::pontoon(),
+        };
+        // With original bets only nothing more is at risk than with a hole card.
+        if rules.peek == PeekRule::NoHoleCard { chart.no_hole_card() } else { chart }
+    }
+
+    /// Reads a chart file, rows it doesn't mention keep the built-in chart for `rules`.
+    pub fn load(path: &str, rules: &Rules) -> Result<Self, BlackjackError> {
+        let text: String = fs::read_to_string(path)
+            .map_err(|e| BlackjackError::Storage(format!("{}: {}", path, e)))?;
+        let mut chart: Chart = Chart::for_rules(rules);
+        chart.name = path.to_string();
+        chart.read(path, &text)?;
+        Ok(chart)
+    }
+
+    /// Overwrites the rows found in `text`, `source` naming it in errors.
+    fn read(&mut self, source: &str, text: &str) -> Result<(), BlackjackError> {
+        for (n, line) in text.lines().enumerate() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || BlackjackError::InvalidConfig(format!("{} line {}: {}", source, n + 1, line));
+            let (row, decisions) = line.split_once(':').ok_or_else(invalid)?;
+            let decisions: Vec<Decision> = decisions
+                .split_whitespace()
+                .map(from_letter)
+                .collect::<Option<Vec<Decision>>>()
+                .filter(|d| d.len() == 10)
+                .ok_or_else(invalid)?;
+            let decisions: [Decision; 10] = decisions.try_into().map_err(|_| invalid())?;
+            match row.split_whitespace().collect::<Vec<&str>>().as_slice() {
+                ["hard", total] => {
+                    let total: usize = total.parse().ok().filter(|t| (5..=21).contains(t)).ok_or_else(invalid)?;
+                    self.hard[total] = decisions;
+                },
+                ["soft", total] => {
+                    let total: usize = total.parse().ok().filter(|t| (13..=21).contains(t)).ok_or_else(invalid)?;
+                    self.soft[total] = decisions;
+                },
+                ["pair", rank] => {
+                    let value: u8 = pair_value(rank).ok_or_else(invalid)?;
+                    self.pairs[value as usize] = decisions;
+                },
+                _ => return Err(invalid())
+            }
+        }
+        Ok(())
+    }
+
+    /// The chart in the text format [`Chart::load`] reads.
+    pub fn to_text(&self) -> String {
+        let header: Vec<String> = UP_CARDS.iter().map(|c| c.to_string()).collect();
+        let mut text: String = format!("# dealer: {}\n", header.join(" "));
+        let row = |name: String, decisions: &[Decision; 10]| {
+            let letters: Vec<String> = decisions.iter().map(|d| letter(*d).to_string()).collect();
+            format!("{}: {}\n", name, letters.join(" "))
+        };
+        for total in 5..=21 {
+            text.push_str(&row(format!("hard {}", total), &self.hard[total]));
+        }
+        for total in 13..=21 {
+            text.push_str(&row(format!("soft {}", total), &self.soft[total]));
+        }
+        for value in 1..=10u8 {
+            text.push_str(&row(format!("pair {}", rank_of(value)), &self.pairs[value as usize]));
+        }
+        text
+    }
+
+    /// Decision from the chart before checking it's legal.
+    pub fn lookup(&self, hand: &Hand, up_card: Card) -> Decision {
+        let value: HandValue = hand.value();
+        match value.pair {
+            Some(rank) => self.pairs[Card::of(rank, '\u{2660}').value() as usize][column(up_card)],
+            None => self.total_row(&value, up_card),
+        }
+    }
+
+    /// Decision for the hand's total, ignoring pairs.
+    fn total_row(&self, value: &HandValue, up_card: Card) -> Decision {
+        let total: usize = value.total.min(21) as usize;
+        if value.soft && total >= 13 {
+            self.soft[total][column(up_card)]
+        } else {
+            self.hard[total.max(5)][column(up_card)]
+        }
+    }
+}
+
+impl Default for Chart {
+    fn default() -> Self {
+        Chart::basic()
+    }
+}
+
+/// Value of the pair named by `rank` in a chart file: A, 2 to 9, T or 10.
+fn pair_value(rank: &str) -> Option<u8> {
+    match rank {
+        "A" => Some(1),
+        "T" | "10" => Some(10),
+        _ => rank.parse().ok().filter(|v| (2..=9).contains(v)),
+    }
+}
+
+fn rank_of(value: u8) -> char {
+    match value {
+        1 => 'A',
+        10 => 'T',
+        _ => (b'0' + value) as char
+    }
+}
+
+impl Strategy for Chart {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn decide(&self, hand: &Hand, dealer: &[Card], legal: &[Decision]) -> Decision {
+        let Some(&up_card) = dealer.first() else {
+            return pontoon_decision(hand, legal);
+        };
+        if hand.is_doubled() {
+            // Double-down rescue: a stiff doubled hand is given up against a strong up card.
+            let rescue: bool = hand.value().total <= 16 && (up_card.value() >= 9 || up_card.value() == 1);
+            return if rescue && legal.contains(&Decision::Surrender) { Decision::Surrender } else { Decision::Stand };
+        }
+        // With the hole card showing the chart is read for what the dealer's total plays like.
+        let exposed: Option<HandValue> = (dealer.len() > 1).then(|| HandValue::new(dealer, false));
+        let column_card: Card = exposed.as_ref().map_or(up_card, exposed_card);
+        let decision: Decision = match exposed.and_then(|d| exposed_decision(hand, &d, legal)) {
+            Some(decision) => decision,
+            None => match self.lookup(hand, column_card) {
+                // A pair that can't be split is played on its total.
+                Decision::Split if !legal.contains(&Decision::Split) => self.total_row(&hand.value(), column_card),
+                decision => decision,
+            },
+        };
+        match decision {
+            Decision::Double if !legal.contains(&Decision::Double) => Decision::Hit,
+            Decision::Split if !legal.contains(&Decision::Split) => Decision::Hit,
+            Decision::Surrender if !legal.contains(&Decision::Surrender) => {
+                if hand.value().total >= 17 { Decision::Stand } else { Decision::Hit }
+            },
+            Decision::None => Decision::Stand,
+            decision => decision,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROW: &str = "S S S S S H H H H R";
+
+    fn read(text: &str) -> Result<Chart, BlackjackError> {
+        let mut chart: Chart = Chart::basic();
+        chart.read("test", text).map(|_| chart)
+    }
+
+    #[test]
+    fn read_overwrites_the_rows_it_names() {
+        let text: String = format!("# dealer: 2 3 4 5 6 7 8 9 T A\n\nhard 16: {ROW}\nsoft 18: {ROW}\npair 8: {ROW}\n");
+        let chart: Chart = read(&text).unwrap();
+        let row: [Decision; 10] = [
+            Decision::Stand, Decision::Stand, Decision::Stand, Decision::Stand, Decision::Stand,
+            Decision::Hit, Decision::Hit, Decision::Hit, Decision::Hit, Decision::Surrender
+        ];
+        assert_eq!(chart.hard[16], row);
+        assert_eq!(chart.soft[18], row);
+        assert_eq!(chart.pairs[8], row);
+        assert_eq!(chart.hard[15], Chart::basic().hard[15]);
+    }
+
+    #[test]
+    fn read_names_ten_pairs_either_way() {
+        for rank in ["T", "10"] {
+            let chart: Chart = read(&format!("pair {rank}: {ROW}")).unwrap();
+            assert_eq!(chart.pairs[10][0], Decision::Stand);
+            assert_eq!(chart.pairs[10][9], Decision::Surrender);
+        }
+        let chart: Chart = read(&format!("pair A: {ROW}")).unwrap();
+        assert_eq!(chart.pairs[1][9], Decision::Surrender);
+    }
+
+    #[test]
+    fn read_rejects_rows_outside_the_chart() {
+        for row in ["hard 4", "hard 22", "soft 12", "soft 22", "pair 1", "pair 11", "pair 8s", "pair !", "split 8"] {
+            assert!(read(&format!("{row}: {ROW}")).is_err(), "{row} was accepted");
+        }
+    }
+
+    #[test]
+    fn read_rejects_malformed_decisions() {
+        for line in ["hard 16 S S S S S H H H H H", "hard 16: S S S S S H H H H", "hard 16: S S S S S H H H H H H", "hard 16: S S S S S H H H H X"] {
+            assert!(read(line).is_err(), "{line} was accepted");
+        }
+    }
+
+    #[test]
+    fn read_errors_name_the_line() {
+        let error: BlackjackError = read(&format!("hard 16: {ROW}\nhard 3: {ROW}")).err().unwrap();
+        assert_eq!(error, BlackjackError::InvalidConfig(format!("test line 2: hard 3: {ROW}")));
+    }
+
+    #[test]
+    fn read_takes_back_what_to_text_writes() {
+        let mut chart: Chart = Chart::switch();
+        chart.read("basic", &Chart::basic().to_text()).unwrap();
+        assert_eq!(chart.to_text(), Chart::basic().to_text());
+    }
+}
@@ -0,0 +1,156 @@
+use blackrust::analysis::{SideBetOdds, WhatIf};
+use blackrust::history::RoundRecord;
+use blackrust::profile::Profile;
+use blackrust::report::Summary;
+use blackrust::{chips_for, Card, DealerHand, Decision, GameStatus, Hand, HandValue, Ledger, Simulation};
+
+/// A card as dealt, the red suits in red when `color` is on.
+pub fn card(card: Card, color: bool) -> String {
+    let rank: String = match card.rank() {
+        'T' => "10".to_string(),
+        rank => rank.to_string()
+    };
+    if color && (card.suit() == '\u{2665}' || card.suit() == '\u{2666}') {
+        format!("\x1b[31;49;1m{}{}\x1b[0m", rank, card.suit())
+    } else {
+        format!("{}{}", rank, card.suit())
+    }
+}
+
+fn cards(cards: &[Card], color: bool) -> String {
+    cards.iter().map(|c| format!("{}, ", card(*c, color))).collect()
+}
+
+pub fn hand(hand: &Hand, color: bool) {
+    println!("{}\nValue: {}", cards(hand.cards(), color), hand.value().total);
+}
+
+pub fn dealer_hand(hand: &DealerHand, color: bool) {
+    print!("{}", cards(hand.visible_cards(), color));
+    if hand.is_revealed() {
+        println!("\nValue: {}", hand.value().total);
+    } else {
+        let hidden: Vec<&str> = vec!["??"; hand.hidden_cards()];
+        println!("{}", hidden.join(", "));
+    }
+}
+
+pub fn money(wallet: u32, bet: u32) {
+    if bet != 0 {
+        let chips: Vec<String> = chips_for(bet).iter().map(|c| c.to_string()).collect();
+        println!("Wallet: {}\nBet: {} ({})", wallet, bet, chips.join(", "))
+    } else {
+        println!("Wallet: {}", wallet)
+    }
+}
+
+pub fn ledger_summary(ledger: &Ledger) {
+    println!(
+        "Session: {:+}, Peak bankroll: {}, Max drawdown: {}",
+        ledger.session_total(),
+        ledger.peak_bankroll(),
+        ledger.max_drawdown()
+    );
+}
+
+pub fn profile(profile: &Profile) {
+    println!(
+        "{}: bankroll {}, {} rounds ({}W/{}T/{}L), net {:+}, strategy accuracy {:.1}%",
+        profile.name,
+        profile.bankroll,
+        profile.stats.rounds,
+        profile.stats.wins,
+        profile.stats.ties,
+        profile.stats.losses,
+        profile.stats.net,
+        profile.stats.accuracy() * 100.0
+    );
+}
+
+pub fn simulation(simulation: &Simulation) {
+    let s: &Summary = &simulation.summary;
+    println!("Strategy: {}, seed: {}", s.strategy, s.seed);
+    println!("Rounds: {}, Hands: {}", s.rounds, s.hands);
+    println!("Wins: {}, Ties: {}, Loses: {}", s.wins, s.ties, s.losses);
+    println!(
+        "Blackjacks: {}, Doubles: {}, Splits: {}, Surrenders: {}, Switches: {}, Insurances: {}",
+        s.blackjacks, s.doubles, s.splits, s.surrenders, s.switches, s.insurances
+    );
+    println!("Wagered: {}, Free bets: {}, Paid: {}, Net: {:+}", s.wagered, s.free_bets, s.payout, s.net);
+    println!("Edge: {:+.3}%, Mean: {:+.3} per round, Std dev: {:.3}", s.edge * 100.0, s.mean_net, s.std_dev);
+    println!("Wallet: {} -> {}, Peak bankroll: {}, Max drawdown: {}", s.starting_wallet, s.final_wallet, s.peak_bankroll, s.max_drawdown);
+    for side in s.side_bets.iter() {
+        println!(
+            "{}: {} bets, {} hits ({:.2}%), Wagered: {}, Paid: {}, Net: {:+}, Edge: {:+.3}%",
+            side.kind.name(), side.bets, side.hits, side.hit_frequency * 100.0, side.wagered, side.payout, side.net,
+            side.edge * 100.0
+        );
+    }
+}
+
+pub fn side_bet_odds(odds: &SideBetOdds) {
+    println!(
+        "{}: house edge {:.3}%, hits {:.3}%",
+        odds.kind.name(),
+        odds.house_edge * 100.0,
+        odds.hit_frequency * 100.0
+    );
+}
+
+pub fn what_if(what_if: &WhatIf) {
+    println!("Decision {}: {:?} instead of {:?}", what_if.decision_index + 1, what_if.alternative, what_if.original);
+    if what_if.evs.is_empty() {
+        println!("No expected values for {:?}", what_if.actual.rules.variant);
+    }
+    for (decision, ev) in what_if.evs.iter() {
+        let estimate: &str = if *decision == Decision::Split { ", estimate" } else { "" };
+        println!("  {:?}: {:+.4} ({:+.2}{})", decision, ev, ev * what_if.bet as f64, estimate);
+    }
+    let split: bool = what_if.original == Decision::Split || what_if.alternative == Decision::Split;
+    println!(
+        "EV difference{}: {:+.4} per unit, {:+.2} on a bet of {}",
+        if split { " (estimate)" } else { "" },
+        what_if.ev_difference(),
+        what_if.ev_difference() * what_if.bet as f64,
+        what_if.bet
+    );
+    let net = |record: &RoundRecord| record.final_wallet as i64 - record.wallet as i64;
+    println!("Actual result: {:+}", net(&what_if.actual));
+    println!("With {:?}: {:+}", what_if.alternative, net(&what_if.forked));
+}
+
+fn recorded_cards(hand: &[Card], color: bool) -> String {
+    format!("{}({})", cards(hand, color), HandValue::new(hand, false).total)
+}
+
+/// Prints a recorded round decision by decision.
+pub fn round(record: &RoundRecord, color: bool) {
+    println!("Round {} (shuffle {}, card {}), bet {}", record.round, record.shuffle, record.shoe_position, record.bet);
+    for bet in record.side_bets.iter() {
+        println!("{}: wager {}, paid {}", bet.kind.name(), bet.wager, bet.payout);
+    }
+    if let Some(switched) = record.switched {
+        println!("Switch: {}", if switched { "second cards swapped" } else { "kept" });
+    }
+    if let Some(take) = record.insurance {
+        println!("Insurance: {}", if take { "taken" } else { "declined" });
+    }
+    for decision in record.decisions.iter() {
+        println!("Hand {}: {}", decision.hand + 1, recorded_cards(&decision.cards, color));
+        let legal: Vec<String> = decision.legal.iter().map(|d| format!("{:?}", d)).collect();
+        println!("  {:?} out of {}", decision.decision, legal.join(", "));
+    }
+    for (i, hand) in record.player_hands.iter().enumerate() {
+        println!("Final hand {}: {}", i + 1, recorded_cards(hand, color));
+    }
+    println!("Dealer: {}", recorded_cards(&record.dealer_hand, color));
+    for result in record.results.iter() {
+        let status: &str = match result.status {
+            GameStatus::Win => "Win",
+            GameStatus::Tie => "Tie",
+            GameStatus::Lose => "Lose",
+        };
+        println!("{}: wager {}, paid {}", status, result.wager, result.payout);
+    }
+    println!("Wallet: {} -> {}", record.wallet, record.final_wallet);
+}